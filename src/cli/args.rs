@@ -1,6 +1,13 @@
 use std::env;
 // use crate::distro::{LinuxDistro, DistroType};
-use crate::system::uninstall_system_by_id;
+use crate::system::{rename_system_by_id, stop_system, uninstall_system_by_id};
+use crate::system::shortcuts::{create_shell_alias, create_shortcut};
+use crate::system::ssh::{connect_ssh, setup_ssh};
+use crate::system::check::check_system;
+use crate::system::repair::repair_system;
+use crate::system::volumes::list_volumes;
+use crate::config::ConfigManager;
+use crate::system::launcher::{login_system, run_in_system};
 use crate::ui::{print_info_theme, print_success_theme};
 use crate::i18n::Translator;
 use crate::ui::colors::Theme;
@@ -16,15 +23,14 @@ pub fn handle_command_line_args(args: &[String], translator: &Translator, theme:
         return Ok(());
     }
     
+    let rest = &args[start_idx + 1..];
+    
     match args[start_idx].as_str() {
-        "--list" => {
-            let metas = crate::utils::get_system_metas()?;
-            crate::ui::display_system_list(&metas, translator)?;
+        "list" | "--list" => {
+            super::output_args::handle_list(rest, translator)?;
         }
-        "--install" => {
-            // TODO: Update to use new distro system
-            println!("\n  {}\n", theme.error(&translator.t("error_specify_distro")));
-            println!("  {}\n", theme.info(&translator.t_fmt("usage_install", &[&args[0]])));
+        "install" | "--install" => {
+            super::install::handle_install(rest, &args[0], translator, theme)?;
         }
         "--uninstall" => {
             if args.len() < 3 {
@@ -37,6 +43,164 @@ pub fn handle_command_line_args(args: &[String], translator: &Translator, theme:
             uninstall_system_by_id(&args[2], translator)?;
             print_success_theme(&translator.t("uninstall_complete"), theme);
         }
+        "login" | "--login" => {
+            let Some(system_id) = rest.first() else {
+                println!("\n  {}\n", theme.error(&translator.t("error_specify_system_id")));
+                println!("  {}\n", theme.info(&translator.t_fmt("usage_login_cmd", &[&args[0]])));
+                return Ok(());
+            };
+            
+            let code = login_system(system_id, translator)?;
+            std::process::exit(code);
+        }
+        "run" => {
+            let command = rest.get(1..).and_then(split_run_command);
+            let (Some(system_id), Some(command)) = (rest.first(), command) else {
+                println!("\n  {}\n", theme.error(&translator.t("error_specify_command")));
+                println!("  {}\n", theme.info(&translator.t_fmt("usage_run_cmd", &[&args[0]])));
+                return Ok(());
+            };
+            
+            let code = run_in_system(system_id, command, translator)?;
+            std::process::exit(code);
+        }
+        "ps" => {
+            super::output_args::handle_ps(rest, translator)?;
+        }
+        "info" => {
+            super::output_args::handle_info(rest, &args[0], translator, theme)?;
+        }
+        "check" | "repair" => {
+            let Some(system_id) = rest.first() else {
+                let usage = if args[start_idx] == "check" { "usage_check_cmd" } else { "usage_repair_cmd" };
+                println!("\n  {}\n", theme.error(&translator.t("error_specify_system_id")));
+                println!("  {}\n", theme.info(&translator.t_fmt(usage, &[&args[0]])));
+                return Ok(());
+            };
+            
+            let items = if args[start_idx] == "repair" {
+                let (repaired, items) = repair_system(system_id, translator)?;
+                for item in &repaired {
+                    print_info_theme(&translator.t_fmt("repaired_item", &[&item.subject]), theme);
+                }
+                items
+            } else {
+                check_system(system_id, translator)?
+            };
+            
+            crate::ui::display_check_results(&items, translator);
+            let problems = items.iter().filter(|item| item.problem.is_some()).count();
+            if problems == 0 {
+                print_success_theme(&translator.t_fmt("check_passed", &[system_id]), theme);
+            } else {
+                println!("  {}\n", theme.error(&translator.t_fmt("check_failed", &[system_id, &problems.to_string()])));
+                std::process::exit(1);
+            }
+        }
+        "config" => {
+            super::config::handle_config(rest, &args[0], translator, theme)?;
+        }
+        "catalog" => {
+            super::catalog::handle_catalog(rest, &args[0], translator, theme)?;
+        }
+        "mirror" => {
+            super::mirror::handle_mirror(rest, &args[0], translator, theme)?;
+        }
+        "search" | "available" => {
+            super::search::handle_search(rest, args[start_idx] == "search", &args[0], translator, theme)?;
+        }
+        "stop" => {
+            let Some(system_id) = rest.first() else {
+                println!("\n  {}\n", theme.error(&translator.t("error_specify_system_id")));
+                println!("  {}\n", theme.info(&translator.t_fmt("usage_stop_cmd", &[&args[0]])));
+                return Ok(());
+            };
+            
+            match stop_system(system_id, translator)? {
+                0 => print_info_theme(&translator.t_fmt("system_not_running", &[system_id]), theme),
+                count => print_success_theme(&translator.t_fmt("sessions_stopped", &[&count.to_string(), system_id]), theme),
+            }
+        }
+        "desktop" => {
+            super::desktop::handle_desktop(rest, &args[0], translator, theme)?;
+        }
+        "service" => {
+            super::service::handle_service(rest, &args[0], translator, theme)?;
+        }
+        "rename" => {
+            let (Some(old_id), Some(new_id)) = (rest.first(), rest.get(1)) else {
+                println!("\n  {}\n", theme.error(&translator.t("error_specify_system_id")));
+                println!("  {}\n", theme.info(&translator.t_fmt("usage_rename_cmd", &[&args[0]])));
+                return Ok(());
+            };
+            
+            rename_system_by_id(old_id, new_id, translator)?;
+            print_success_theme(&translator.t_fmt("system_renamed", &[old_id, new_id]), theme);
+        }
+        "shortcut" => {
+            let Some(system_id) = rest.first() else {
+                println!("\n  {}\n", theme.error(&translator.t("error_specify_system_id")));
+                println!("  {}\n", theme.info(&translator.t_fmt("usage_shortcut_cmd", &[&args[0]])));
+                return Ok(());
+            };
+            if !crate::utils::fs::get_system_dir(system_id)?.exists() {
+                println!("\n  {}\n", theme.error(&translator.t_fmt("system_not_exist", &[system_id])));
+                return Ok(());
+            }
+            
+            let script = create_shortcut(system_id)?;
+            print_success_theme(&translator.t_fmt("shortcut_created", &[&script.display().to_string()]), theme);
+            if rest.iter().any(|arg| arg == "--alias") {
+                let rc_path = create_shell_alias(system_id)?;
+                print_info_theme(&translator.t_fmt("shell_alias_added", &[system_id, &rc_path.display().to_string()]), theme);
+            }
+        }
+        "ssh-setup" => {
+            let Some(system_id) = rest.first() else {
+                println!("\n  {}\n", theme.error(&translator.t("error_specify_system_id")));
+                println!("  {}\n", theme.info(&translator.t_fmt("usage_ssh_setup_cmd", &[&args[0]])));
+                return Ok(());
+            };
+            let extra_key = match rest.get(1).map(String::as_str) {
+                Some("--key") => match rest.get(2) {
+                    Some(path) => Some(std::path::PathBuf::from(path)),
+                    None => {
+                        println!("\n  {}\n", theme.error(&translator.t_fmt("error_missing_option_value", &["--key"])));
+                        return Ok(());
+                    }
+                },
+                Some(other) => {
+                    println!("\n  {}\n", theme.error(&translator.t_fmt("unknown_argument", &[other])));
+                    return Ok(());
+                }
+                None => None,
+            };
+            
+            let port = setup_ssh(system_id, extra_key.as_ref(), translator)?;
+            print_success_theme(&translator.t_fmt("ssh_ready", &[&port.to_string()]), theme);
+            print_info_theme(&translator.t_fmt("ssh_connect_hint", &[&args[0], system_id, &port.to_string()]), theme);
+        }
+        "ssh" => {
+            let Some(system_id) = rest.first() else {
+                println!("\n  {}\n", theme.error(&translator.t("error_specify_system_id")));
+                println!("  {}\n", theme.info(&translator.t_fmt("usage_ssh_cmd", &[&args[0]])));
+                return Ok(());
+            };
+            
+            let ssh_args = rest.get(1..).and_then(split_run_command).unwrap_or_default();
+            let code = connect_ssh(system_id, &ssh_args, translator)?;
+            std::process::exit(code);
+        }
+        "volume" => match rest.first().map(String::as_str) {
+            Some("list") | None => {
+                let volumes = list_volumes(&ConfigManager::new()?)?;
+                crate::ui::display_volumes(&volumes, translator);
+            }
+            Some(other) => {
+                println!("\n  {}\n", theme.error(&translator.t_fmt("unknown_argument", &[other])));
+                println!("  {}\n", theme.info(&translator.t_fmt("usage_volume_cmd", &[&args[0]])));
+            }
+        },
         "--help" => {
             display_help(translator, theme);
         }
@@ -52,15 +216,69 @@ pub fn handle_command_line_args(args: &[String], translator: &Translator, theme:
     Ok(())
 }
 
+fn split_run_command(args: &[String]) -> Option<Vec<String>> {
+    let command = match args.first() {
+        Some(first) if first == "--" => &args[1..],
+        _ => args,
+    };
+    
+    if command.is_empty() {
+        None
+    } else {
+        Some(command.to_vec())
+    }
+}
+
 fn display_help(translator: &Translator, theme: &Theme) {
     let program_name = env::args().next().unwrap_or_else(|| "insOs".to_string());
     
     println!("\n  {}\n    {}", theme.info(&translator.t("usage_header")), theme.info(&translator.t_fmt("usage_interactive", &[&program_name])));
     println!("    {}", theme.info(&translator.t_fmt("usage_list", &[&program_name])));
     println!("    {}", theme.info(&translator.t_fmt("usage_install_cmd", &[&program_name])));
+    println!("    {}", theme.info(&translator.t_fmt("usage_install_arch", &[&program_name])));
+    println!("    {}", theme.info(&translator.t_fmt("usage_install_release", &[&program_name])));
+    println!("    {}", theme.info(&translator.t_fmt("usage_search_cmd", &[&program_name])));
+    println!("    {}", theme.info(&translator.t_fmt("usage_available_cmd", &[&program_name])));
     println!("    {}", theme.info(&translator.t_fmt("usage_uninstall_cmd", &[&program_name])));
+    println!("    {}", theme.info(&translator.t_fmt("usage_login_cmd", &[&program_name])));
+    println!("    {}", theme.info(&translator.t_fmt("usage_run_cmd", &[&program_name])));
+    println!("    {}", theme.info(&translator.t_fmt("usage_ps_cmd", &[&program_name])));
+    println!("    {}", theme.info(&translator.t_fmt("usage_info_cmd", &[&program_name])));
+    println!("    {}", theme.info(&translator.t_fmt("usage_check_cmd", &[&program_name])));
+    println!("    {}", theme.info(&translator.t_fmt("usage_repair_cmd", &[&program_name])));
+    println!("    {}", theme.info(&translator.t_fmt("usage_stop_cmd", &[&program_name])));
+    println!("    {}", theme.info(&translator.t_fmt("usage_service_cmd", &[&program_name])));
+    println!("    {}", theme.info(&translator.t_fmt("usage_desktop_cmd", &[&program_name])));
+    println!("    {}", theme.info(&translator.t_fmt("usage_volume_cmd", &[&program_name])));
+    println!("    {}", theme.info(&translator.t_fmt("usage_shortcut_cmd", &[&program_name])));
+    println!("    {}", theme.info(&translator.t_fmt("usage_rename_cmd", &[&program_name])));
+    println!("    {}", theme.info(&translator.t_fmt("usage_ssh_setup_cmd", &[&program_name])));
+    println!("    {}", theme.info(&translator.t_fmt("usage_ssh_cmd", &[&program_name])));
+    println!("    {}", theme.info(&translator.t_fmt("usage_config_cmd", &[&program_name])));
+    println!("    {}", theme.info(&translator.t_fmt("usage_catalog_cmd", &[&program_name])));
+    println!("    {}", theme.info(&translator.t_fmt("usage_mirror_cmd", &[&program_name])));
     println!("    {}\n", theme.info(&translator.t_fmt("usage_help", &[&program_name])));
     println!("  {}\n", theme.info(&translator.t("supported_distros")));
     println!("  {}\n    {}", theme.info(&translator.t("install_options")), theme.info(&translator.t("option_name")));
-    println!("    {}\n", theme.info(&translator.t("option_minimal")));
-}
\ No newline at end of file
+    println!("    {}", theme.info(&translator.t("option_minimal")));
+    println!("    {}", theme.info(&translator.t("option_arch")));
+    println!("    {}\n", theme.info(&translator.t("option_ignore_space")));
+    println!("  {}\n", theme.info(&translator.t("option_output")));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_split_run_command() {
+        assert_eq!(split_run_command(&strings(&["--", "ls", "-la"])), Some(strings(&["ls", "-la"])));
+        assert_eq!(split_run_command(&strings(&["uname", "-a"])), Some(strings(&["uname", "-a"])));
+        assert_eq!(split_run_command(&strings(&["--"])), None);
+        assert_eq!(split_run_command(&[]), None);
+    }
+}
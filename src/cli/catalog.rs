@@ -0,0 +1,99 @@
+use std::path::Path;
+use crate::config::ConfigManager;
+use crate::distro::definitions::catalog::{append_catalog_entry, render_entry, user_catalog_path};
+use crate::distro::definitions::catalog_update::{trusted_catalog_keys, update_catalog, CatalogUpdate};
+use crate::distro::definitions::plugin::parse_plugin;
+use crate::distro::DistroName;
+use crate::i18n::Translator;
+use crate::ui::colors::Theme;
+use crate::ui::{print_info_theme, print_success_theme};
+
+pub fn handle_catalog(args: &[String], program: &str, translator: &Translator, theme: &Theme) -> Result<(), Box<dyn std::error::Error>> {
+    match args.first().map(String::as_str) {
+        Some("update") => {
+            let config = ConfigManager::new()?.load_config()?;
+            let Some(url) = config.get("catalog_url") else {
+                println!("\n  {}\n", theme.error(&translator.t_fmt("catalog_setting_missing", &["catalog_url"])));
+                return Ok(());
+            };
+            let public_keys = trusted_catalog_keys(config.get("catalog_key").map(String::as_str));
+            if public_keys.is_empty() {
+                println!("\n  {}\n", theme.error(&translator.t_fmt("catalog_setting_missing", &["catalog_key"])));
+                return Ok(());
+            }
+
+            print_info_theme(&translator.t_fmt("catalog_updating", &[url]), theme);
+            match update_catalog(url, &public_keys)? {
+                CatalogUpdate::Updated { serial, distros } => {
+                    print_success_theme(&translator.t_fmt("catalog_updated", &[&serial.to_string(), &distros.to_string()]), theme);
+                }
+                CatalogUpdate::UpToDate { serial } => {
+                    print_info_theme(&translator.t_fmt("catalog_up_to_date", &[&serial.to_string()]), theme);
+                }
+            }
+        }
+        Some("import-plugin") => return import_plugin(&args[1..], program, translator, theme),
+        other => {
+            if let Some(other) = other {
+                println!("\n  {}\n", theme.error(&translator.t_fmt("unknown_argument", &[other])));
+            }
+            println!("  {}\n", theme.info(&translator.t_fmt("usage_catalog_cmd", &[program])));
+        }
+    }
+    Ok(())
+}
+
+/// `catalog import-plugin <file.sh> [--id <id>] [--print]`: the distro id
+/// defaults to the plugin's file name, as in proot-distro.
+fn import_plugin(args: &[String], program: &str, translator: &Translator, theme: &Theme) -> Result<(), Box<dyn std::error::Error>> {
+    let mut file = None;
+    let mut id = None;
+    let mut print_only = false;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--id" => match iter.next() {
+                Some(value) => id = Some(value.clone()),
+                None => {
+                    println!("\n  {}\n", theme.error(&translator.t_fmt("error_missing_option_value", &["--id"])));
+                    return Ok(());
+                }
+            },
+            "--print" => print_only = true,
+            other if file.is_none() && !other.starts_with("--") => file = Some(other.to_string()),
+            other => {
+                println!("\n  {}\n", theme.error(&translator.t_fmt("unknown_argument", &[other])));
+                return Ok(());
+            }
+        }
+    }
+
+    let Some(file) = file else {
+        println!("\n  {}\n", theme.error(&translator.t("error_specify_plugin_file")));
+        println!("  {}\n", theme.info(&translator.t_fmt("usage_catalog_cmd", &[program])));
+        return Ok(());
+    };
+    let id = id.unwrap_or_else(|| {
+        let name = Path::new(&file).file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+        name.strip_suffix(".sh").unwrap_or(&name).to_string()
+    });
+    let Some(name) = DistroName::from_str(&id) else {
+        println!("\n  {}\n", theme.error(&translator.t_fmt("error_invalid_distro_id", &[&id])));
+        return Ok(());
+    };
+
+    let distro = parse_plugin(&std::fs::read_to_string(&file)?, &file, name)?;
+    if print_only {
+        print!("{}", render_entry(&distro));
+        return Ok(());
+    }
+
+    let catalog_path = user_catalog_path()?;
+    let catalog_file = catalog_path.display().to_string();
+    if append_catalog_entry(&catalog_path, &distro)? {
+        print_success_theme(&translator.t_fmt("catalog_plugin_imported", &[&distro.display_name, &catalog_file]), theme);
+    } else {
+        println!("\n  {}\n", theme.error(&translator.t_fmt("catalog_entry_exists", &[&id, &catalog_file])));
+    }
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use super::output_args::{report_usage_error, take_output_or_exit};
+use crate::config::ConfigManager;
+use crate::i18n::Translator;
+use crate::ui::colors::Theme;
+use crate::ui::output::{config_record, print_record, CONFIG_FIELDS};
+use crate::ui::print_info_theme;
+
+pub fn handle_config(args: &[String], program: &str, translator: &Translator, theme: &Theme) -> Result<(), Box<dyn std::error::Error>> {
+    let (format, args) = take_output_or_exit(args, translator);
+
+    let (Some("get"), Some(key)) = (args.first().map(String::as_str), args.get(1)) else {
+        report_usage_error(format, &translator.t("error_specify_config_key"), Some(&translator.t_fmt("usage_config_cmd", &[program])), theme);
+        return Ok(());
+    };
+
+    let config_manager = ConfigManager::new()?;
+    let value = match args.get(2).map(String::as_str) {
+        Some("--system") => match args.get(3) {
+            Some(system_id) => config_manager.get_system_setting(system_id, key)?,
+            None => {
+                report_usage_error(format, &translator.t_fmt("error_missing_option_value", &["--system"]), None, theme);
+                return Ok(());
+            }
+        },
+        Some(other) => {
+            report_usage_error(format, &translator.t_fmt("unknown_argument", &[other]), None, theme);
+            return Ok(());
+        }
+        None => config_manager.load_config()?.get(key.as_str()).cloned(),
+    };
+
+    if format.is_machine() {
+        print_record(format, &CONFIG_FIELDS, &config_record(key, value));
+        return Ok(());
+    }
+
+    match value {
+        Some(value) => println!("{}", value),
+        None => print_info_theme(&translator.t_fmt("config_key_not_set", &[key]), theme),
+    }
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use crate::i18n::Translator;
+use crate::system::desktop::{start_desktop, DesktopEnvironment};
+use crate::ui::colors::Theme;
+
+pub fn handle_desktop(args: &[String], program: &str, translator: &Translator, theme: &Theme) -> Result<(), Box<dyn std::error::Error>> {
+    let mut system_id = None;
+    let mut desktop = None;
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--de" => {
+                let Some(value) = iter.next() else {
+                    println!("\n  {}\n", theme.error(&translator.t_fmt("error_missing_option_value", &[arg])));
+                    return Ok(());
+                };
+                let Some(parsed) = DesktopEnvironment::from_str(value) else {
+                    println!("\n  {}\n", theme.error(&translator.t_fmt("error_unknown_desktop", &[value])));
+                    return Ok(());
+                };
+                desktop = Some(parsed);
+            }
+            other if system_id.is_none() && !other.starts_with("--") => system_id = Some(other),
+            other => {
+                println!("\n  {}\n", theme.error(&translator.t_fmt("unknown_argument", &[other])));
+                return Ok(());
+            }
+        }
+    }
+
+    let Some(system_id) = system_id else {
+        println!("\n  {}\n", theme.error(&translator.t("error_specify_system_id")));
+        println!("  {}\n", theme.info(&translator.t_fmt("usage_desktop_cmd", &[program])));
+        return Ok(());
+    };
+
+    let code = start_desktop(system_id, desktop, translator)?;
+    std::process::exit(code);
+}
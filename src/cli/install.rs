@@ -0,0 +1,145 @@
+use crate::distro::{get_distro_by_name, load_catalog};
+use crate::i18n::Translator;
+use crate::installer::core::install_distro;
+use crate::ui::colors::Theme;
+use crate::utils::arch::{find_qemu_binary, get_architecture, Architecture};
+
+#[derive(Debug, PartialEq)]
+pub struct InstallOptions {
+    pub distro: String,
+    pub name: Option<String>,
+    pub minimal: bool,
+    pub arch: Option<Architecture>,
+    pub release: Option<String>,
+    pub ignore_space: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum InstallArgError {
+    MissingDistro,
+    MissingValue(String),
+    UnknownArch(String),
+    UnknownOption(String),
+}
+
+pub fn parse_install_args(args: &[String]) -> Result<InstallOptions, InstallArgError> {
+    let mut distro = None;
+    let mut name = None;
+    let mut minimal = false;
+    let mut arch = None;
+    let mut release = None;
+    let mut ignore_space = false;
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--name" | "--arch" | "--release" => {
+                let value = iter.next().ok_or_else(|| InstallArgError::MissingValue(arg.clone()))?;
+                match arg.as_str() {
+                    "--name" => name = Some(value.clone()),
+                    "--release" => release = Some(value.clone()),
+                    _ => {
+                        arch = Some(Architecture::from_str(value)
+                            .ok_or_else(|| InstallArgError::UnknownArch(value.clone()))?);
+                    }
+                }
+            }
+            "--minimal" => minimal = true,
+            "--ignore-space" => ignore_space = true,
+            other if other.starts_with("--") => return Err(InstallArgError::UnknownOption(other.to_string())),
+            other if distro.is_none() => distro = Some(other.to_string()),
+            other => return Err(InstallArgError::UnknownOption(other.to_string())),
+        }
+    }
+
+    Ok(InstallOptions {
+        distro: distro.ok_or(InstallArgError::MissingDistro)?,
+        name,
+        minimal,
+        arch,
+        release,
+        ignore_space,
+    })
+}
+
+pub fn handle_install(args: &[String], program: &str, translator: &Translator, theme: &Theme) -> Result<(), Box<dyn std::error::Error>> {
+    let options = match parse_install_args(args) {
+        Ok(options) => options,
+        Err(err) => {
+            let message = match err {
+                InstallArgError::MissingDistro => translator.t("error_specify_distro"),
+                InstallArgError::MissingValue(option) => translator.t_fmt("error_missing_option_value", &[&option]),
+                InstallArgError::UnknownArch(arch) => translator.t_fmt("error_unknown_arch", &[&arch]),
+                InstallArgError::UnknownOption(option) => translator.t_fmt("unknown_argument", &[&option]),
+            };
+            println!("\n  {}\n", theme.error(&message));
+            println!("  {}\n", theme.info(&translator.t_fmt("usage_install", &[program])));
+            return Ok(());
+        }
+    };
+
+    if let Err(e) = load_catalog() {
+        println!("\n  {}", theme.error(&translator.t_fmt("catalog_invalid", &[&e.to_string()])));
+    }
+
+    let Some(mut distro_def) = get_distro_by_name(&options.distro) else {
+        println!("\n  {}: {}\n", theme.error(&translator.t("error_unsupported_distro")), options.distro);
+        return Ok(());
+    };
+    if let Some(release) = &options.release {
+        let Some(selected) = distro_def.with_release(release) else {
+            let available: Vec<&str> = distro_def.releases.iter().map(|r| r.name.as_str()).collect();
+            let available = if available.is_empty() { "-".to_string() } else { available.join(", ") };
+            println!("\n  {}\n", theme.error(&translator.t_fmt("error_unknown_release", &[release, &distro_def.display_name, &available])));
+            return Ok(());
+        };
+        distro_def = selected;
+    }
+
+    let host_arch = get_architecture(translator)?;
+    let target_arch = options.arch.unwrap_or(host_arch);
+    let emulated = !host_arch.can_run_natively(&target_arch);
+
+    if !distro_def.supports_arch(&target_arch) {
+        println!("\n  {}\n", theme.error(&translator.t_fmt("error_distro_arch_unsupported", &[&distro_def.label(), target_arch.to_str()])));
+        return Ok(());
+    }
+    if emulated && find_qemu_binary(&target_arch).is_none() {
+        println!("\n  {}\n", theme.error(&translator.t_fmt("qemu_not_found", &[target_arch.qemu_binary()])));
+        return Ok(());
+    }
+
+    let mode = if options.minimal { "minimal" } else { "standard" };
+    install_distro(&distro_def, options.name, mode, &target_arch, emulated, options.ignore_space, translator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_install_args_full() {
+        let options = parse_install_args(&strings(&["debian", "--name", "deb-x86", "--arch", "amd64", "--minimal", "--release", "bookworm", "--ignore-space"])).unwrap();
+        assert_eq!(options, InstallOptions {
+            distro: "debian".to_string(),
+            name: Some("deb-x86".to_string()),
+            minimal: true,
+            arch: Some(Architecture::X86_64),
+            release: Some("bookworm".to_string()),
+            ignore_space: true,
+        });
+    }
+
+    #[test]
+    fn test_parse_install_args_errors() {
+        assert_eq!(parse_install_args(&[]), Err(InstallArgError::MissingDistro));
+        assert_eq!(parse_install_args(&strings(&["debian", "--arch"])), Err(InstallArgError::MissingValue("--arch".to_string())));
+        assert_eq!(parse_install_args(&strings(&["debian", "--release"])), Err(InstallArgError::MissingValue("--release".to_string())));
+        assert_eq!(parse_install_args(&strings(&["debian", "--arch", "sparc"])), Err(InstallArgError::UnknownArch("sparc".to_string())));
+        assert_eq!(parse_install_args(&strings(&["debian", "--foo"])), Err(InstallArgError::UnknownOption("--foo".to_string())));
+    }
+}
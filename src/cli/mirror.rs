@@ -0,0 +1,104 @@
+use crate::config::mirror_bench::{bench_mirrors, BENCH_TIMEOUT_SECS};
+use crate::config::ConfigManager;
+use crate::distro::package_manager_for;
+use crate::distro::DistroName;
+use crate::i18n::Translator;
+use crate::system::mirror::{apply_mirror, reset_mirror};
+use crate::ui::colors::Theme;
+use crate::ui::{display_mirror_bench, print_info_theme, print_success_theme};
+use crate::utils::fs::{get_system_dir, load_system_meta};
+
+/// `mirror set <id> <url>`, `mirror reset <id>` and `mirror bench <distro> [--write]`.
+pub fn handle_mirror(args: &[String], program: &str, translator: &Translator, theme: &Theme) -> Result<(), Box<dyn std::error::Error>> {
+    let usage = || println!("  {}\n", theme.info(&translator.t_fmt("usage_mirror_cmd", &[program])));
+    if args.first().map(String::as_str) == Some("bench") {
+        let Some(distro) = args.get(1).filter(|arg| !arg.starts_with("--")) else {
+            println!("\n  {}\n", theme.error(&translator.t("error_specify_mirror_distro")));
+            usage();
+            return Ok(());
+        };
+        let write = match args.get(2).map(String::as_str) {
+            None => false,
+            Some("--write") => true,
+            Some(other) => {
+                println!("\n  {}\n", theme.error(&translator.t_fmt("unknown_argument", &[other])));
+                usage();
+                return Ok(());
+            }
+        };
+        return bench(distro, write, translator, theme);
+    }
+
+    let (action, system_id) = match (args.first().map(String::as_str), args.get(1)) {
+        (Some(action @ ("set" | "reset")), Some(system_id)) => (action, system_id),
+        (Some("set" | "reset") | None, _) => {
+            println!("\n  {}\n", theme.error(&translator.t("error_specify_system_id")));
+            usage();
+            return Ok(());
+        }
+        (Some(other), _) => {
+            println!("\n  {}\n", theme.error(&translator.t_fmt("unknown_argument", &[other])));
+            usage();
+            return Ok(());
+        }
+    };
+
+    let rootfs = get_system_dir(system_id)?;
+    if !rootfs.exists() {
+        return Err(translator.t_fmt("system_not_exist", &[system_id]).into());
+    }
+
+    if action == "reset" {
+        match reset_mirror(&rootfs)? {
+            0 => print_info_theme(&translator.t_fmt("mirror_reset_nothing", &[system_id]), theme),
+            files => print_success_theme(&translator.t_fmt("mirror_reset", &[system_id, &files.to_string()]), theme),
+        }
+        return Ok(());
+    }
+
+    let Some(mirror) = args.get(2) else {
+        println!("\n  {}\n", theme.error(&translator.t("error_specify_mirror")));
+        usage();
+        return Ok(());
+    };
+    let distro = load_system_meta(system_id).map(|meta| meta.distro).unwrap_or_default();
+    let manager = package_manager_for(&rootfs, &distro)
+        .ok_or_else(|| translator.t_fmt("package_manager_not_found", &[system_id]))?;
+
+    match apply_mirror(&rootfs, manager, mirror)? {
+        0 => println!("\n  {}\n", theme.error(&translator.t_fmt("mirror_not_matched", &[mirror]))),
+        files => print_success_theme(&translator.t_fmt("mirror_applied", &[mirror, &files.to_string()]), theme),
+    }
+    Ok(())
+}
+
+/// Ranks the candidate mirrors of `distro` and, with `--write`, stores the
+/// fastest one as `<distro>-mirror`.
+fn bench(distro: &str, write: bool, translator: &Translator, theme: &Theme) -> Result<(), Box<dyn std::error::Error>> {
+    let Some(distro) = DistroName::from_str(distro) else {
+        return Err(translator.t_fmt("error_invalid_distro_id", &[distro]).into());
+    };
+    let distro = distro.as_str();
+    let config_manager = ConfigManager::new()?;
+    let candidates = config_manager.get_mirror_candidates_for_distro(distro)?;
+    if candidates.is_empty() {
+        print_info_theme(&translator.t_fmt("mirror_bench_no_candidates", &[distro]), theme);
+        return Ok(());
+    }
+
+    print_info_theme(&translator.t_fmt("mirror_bench_running", &[&candidates.len().to_string(), distro]), theme);
+    let probe_path = config_manager.get_mirror_probe_for_distro(distro)?;
+    let results = bench_mirrors(&candidates, &probe_path, BENCH_TIMEOUT_SECS);
+    display_mirror_bench(&results, translator);
+
+    if write {
+        match results.first().filter(|fastest| fastest.probe.is_ok()) {
+            Some(fastest) => {
+                config_manager.set_config_value(&format!("{}-mirror", distro), &fastest.mirror)?;
+                print_success_theme(&translator.t_fmt("mirror_bench_written", &[&fastest.mirror, distro]), theme);
+            }
+            None => return Err(translator.t("mirror_bench_all_failed").into()),
+        }
+    }
+    Ok(())
+}
@@ -1,5 +1,13 @@
 pub mod args;
 pub mod interactive;
+pub mod install;
+pub mod service;
+pub mod desktop;
+pub mod config;
+pub mod catalog;
+pub mod mirror;
+pub mod search;
+pub mod output_args;
 
 pub use args::*;
 
@@ -61,8 +69,9 @@ pub fn run_cli(translator: &Translator, theme: &Theme) -> Result<(), Box<dyn std
                 }
             }
             3 => {
-                let metas = get_system_metas()?;
-                display_system_list(&metas, translator)?;
+                let (mut metas, invalid) = get_system_metas()?;
+                crate::system::disk_usage::update_disk_usage(&mut metas, false)?;
+                display_system_list(&metas, &invalid, translator)?;
             }
             4 => {
                 println!("\n  {}\n", translator.t("exiting_program"));
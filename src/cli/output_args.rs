@@ -0,0 +1,75 @@
+use crate::i18n::Translator;
+use crate::system::disk_usage::update_disk_usage;
+use crate::system::info::collect_system_info;
+use crate::system::session::list_all_sessions;
+use crate::ui::colors::Theme;
+use crate::ui::output::{info_record, print_record, print_records, session_record, system_record, take_output_format, OutputFormat, INFO_FIELDS, SESSION_FIELDS, SYSTEM_FIELDS};
+
+/// Strips `--output`. An unknown format is a scripting error, so it goes to
+/// stderr and the process exits with a failure status.
+pub fn take_output_or_exit(args: &[String], translator: &Translator) -> (OutputFormat, Vec<String>) {
+    take_output_format(args).unwrap_or_else(|value| {
+        eprintln!("{}", translator.t_fmt("invalid_output_format", &[&value]));
+        std::process::exit(1);
+    })
+}
+
+/// Reports a usage error. With `--output json|tsv` the plain message goes to
+/// stderr and the process fails; otherwise it is shown with the usage line.
+pub fn report_usage_error(format: OutputFormat, message: &str, usage: Option<&str>, theme: &Theme) {
+    if format.is_machine() {
+        eprintln!("{}", message);
+        std::process::exit(1);
+    }
+    println!("\n  {}\n", theme.error(message));
+    if let Some(usage) = usage {
+        println!("  {}\n", theme.info(usage));
+    }
+}
+
+/// `list [--refresh] [--output <fmt>]`.
+pub fn handle_list(args: &[String], translator: &Translator) -> Result<(), Box<dyn std::error::Error>> {
+    let (format, rest) = take_output_or_exit(args, translator);
+    let (mut metas, invalid) = crate::utils::get_system_metas()?;
+    update_disk_usage(&mut metas, rest.iter().any(|arg| arg == "--refresh"))?;
+    if format.is_machine() {
+        let records: Vec<_> = metas.iter().map(|(id, meta)| system_record(id, meta)).collect();
+        print_records(format, &SYSTEM_FIELDS, &records);
+        for (system_id, error) in &invalid {
+            eprintln!("{}", translator.t_fmt("invalid_metadata_warning", &[system_id, error]));
+        }
+    } else {
+        crate::ui::display_system_list(&metas, &invalid, translator)?;
+    }
+    Ok(())
+}
+
+/// `ps [--output <fmt>]`.
+pub fn handle_ps(args: &[String], translator: &Translator) -> Result<(), Box<dyn std::error::Error>> {
+    let (format, _) = take_output_or_exit(args, translator);
+    let sessions = list_all_sessions()?;
+    if format.is_machine() {
+        let records: Vec<_> = sessions.iter().map(|(id, session)| session_record(id, session)).collect();
+        print_records(format, &SESSION_FIELDS, &records);
+    } else {
+        crate::ui::display_sessions(&sessions, translator);
+    }
+    Ok(())
+}
+
+/// `info <system-id> [--output <fmt>]`.
+pub fn handle_info(args: &[String], program: &str, translator: &Translator, theme: &Theme) -> Result<(), Box<dyn std::error::Error>> {
+    let (format, rest) = take_output_or_exit(args, translator);
+    let Some(system_id) = rest.first() else {
+        report_usage_error(format, &translator.t("error_specify_system_id"), Some(&translator.t_fmt("usage_info_cmd", &[program])), theme);
+        return Ok(());
+    };
+
+    let info = collect_system_info(system_id, translator)?;
+    if format.is_machine() {
+        print_record(format, &INFO_FIELDS, &info_record(&info));
+    } else {
+        crate::ui::display_system_info(&info, translator);
+    }
+    Ok(())
+}
@@ -0,0 +1,136 @@
+use crate::distro::{load_catalog, search_distros, DistroDefinition, DistroFilter, PackageManager};
+use crate::i18n::Translator;
+use crate::ui::colors::Theme;
+use crate::ui::display_available_distros;
+use crate::utils::arch::{detect_architecture, Architecture};
+use crate::utils::net::get_content_length;
+use crate::config::ConfigManager;
+use crate::installer::core::download_source;
+
+#[derive(Debug, PartialEq)]
+pub enum FilterArgError {
+    MissingValue(String),
+    UnknownArch(String),
+    UnknownFamily(String),
+    UnknownOption(String),
+}
+
+/// Reads `--arch` and `--family`; any other argument is the search term,
+/// which only `search` accepts.
+pub fn parse_filter_args(args: &[String], accept_term: bool) -> Result<DistroFilter, FilterArgError> {
+    let mut filter = DistroFilter::default();
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--arch" | "--family" => {
+                let value = iter.next().ok_or_else(|| FilterArgError::MissingValue(arg.clone()))?;
+                if arg == "--arch" {
+                    filter.arch = Some(Architecture::from_str(value).ok_or_else(|| FilterArgError::UnknownArch(value.clone()))?);
+                } else {
+                    filter.family = Some(PackageManager::from_str(&value.to_lowercase())
+                        .ok_or_else(|| FilterArgError::UnknownFamily(value.clone()))?);
+                }
+            }
+            other if other.starts_with("--") => return Err(FilterArgError::UnknownOption(other.to_string())),
+            other if accept_term && filter.term.is_none() => filter.term = Some(other.to_string()),
+            other => return Err(FilterArgError::UnknownOption(other.to_string())),
+        }
+    }
+    Ok(filter)
+}
+
+/// `search <term> [--arch X] [--family F]` and `available [--arch X] [--family F]`.
+pub fn handle_search(args: &[String], search: bool, program: &str, translator: &Translator, theme: &Theme) -> Result<(), Box<dyn std::error::Error>> {
+    let usage_key = if search { "usage_search_cmd" } else { "usage_available_cmd" };
+    let report = |message: String| {
+        println!("\n  {}\n", theme.error(&message));
+        println!("  {}\n", theme.info(&translator.t_fmt(usage_key, &[program])));
+    };
+
+    let filter = match parse_filter_args(args, search) {
+        Ok(filter) if search && filter.term.is_none() => {
+            report(translator.t("error_specify_search_term"));
+            return Ok(());
+        }
+        Ok(filter) => filter,
+        Err(err) => {
+            report(match err {
+                FilterArgError::MissingValue(option) => translator.t_fmt("error_missing_option_value", &[&option]),
+                FilterArgError::UnknownArch(arch) => translator.t_fmt("error_unknown_arch", &[&arch]),
+                FilterArgError::UnknownFamily(family) => translator.t_fmt("error_unknown_family", &[&family]),
+                FilterArgError::UnknownOption(option) => translator.t_fmt("unknown_argument", &[&option]),
+            });
+            return Ok(());
+        }
+    };
+
+    if let Err(e) = load_catalog() {
+        println!("\n  {}", theme.error(&translator.t_fmt("catalog_invalid", &[&e.to_string()])));
+    }
+
+    let distros = search_distros(&filter);
+    if distros.is_empty() {
+        println!("\n[ {} ]\n", translator.t("no_distros_matched"));
+        return Ok(());
+    }
+
+    let size_arch = filter.arch.or_else(|| detect_architecture().ok());
+    let sizes = match size_arch {
+        Some(arch) => download_sizes(&distros, &arch),
+        None => vec![None; distros.len()],
+    };
+    display_available_distros(&distros, &sizes, size_arch.as_ref(), translator);
+    Ok(())
+}
+
+/// Listing sizes is a nicety, so a slow host only costs this long.
+const SIZE_LOOKUP_TIMEOUT_SECS: u64 = 5;
+
+/// Download size of each distro's archive for `arch`, looked up concurrently
+/// from the URLs `install` would use.
+fn download_sizes(distros: &[DistroDefinition], arch: &Architecture) -> Vec<Option<u64>> {
+    let Ok(config_manager) = ConfigManager::new() else {
+        return vec![None; distros.len()];
+    };
+    let config_manager = &config_manager;
+    std::thread::scope(|scope| {
+        let lookups: Vec<_> = distros
+            .iter()
+            .map(|distro| scope.spawn(move || {
+                let source = download_source(config_manager, distro, arch).ok().flatten()?;
+                source.candidates.iter().find_map(|candidate| get_content_length(candidate, SIZE_LOOKUP_TIMEOUT_SECS).ok().flatten())
+            }))
+            .collect();
+        lookups.into_iter().map(|lookup| lookup.join().ok().flatten()).collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_filter_args() {
+        let filter = parse_filter_args(&strings(&["debian", "--arch", "arm64", "--family", "APT"]), true).unwrap();
+        assert_eq!(filter, DistroFilter {
+            term: Some("debian".to_string()),
+            arch: Some(Architecture::Aarch64),
+            family: Some(PackageManager::Apt),
+        });
+        assert_eq!(parse_filter_args(&[], false).unwrap(), DistroFilter::default());
+    }
+
+    #[test]
+    fn test_parse_filter_args_errors() {
+        assert_eq!(parse_filter_args(&strings(&["debian"]), false), Err(FilterArgError::UnknownOption("debian".to_string())));
+        assert_eq!(parse_filter_args(&strings(&["a", "b"]), true), Err(FilterArgError::UnknownOption("b".to_string())));
+        assert_eq!(parse_filter_args(&strings(&["--family"]), false), Err(FilterArgError::MissingValue("--family".to_string())));
+        assert_eq!(parse_filter_args(&strings(&["--family", "rpm"]), false), Err(FilterArgError::UnknownFamily("rpm".to_string())));
+        assert_eq!(parse_filter_args(&strings(&["--arch", "sparc"]), false), Err(FilterArgError::UnknownArch("sparc".to_string())));
+    }
+}
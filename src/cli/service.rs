@@ -0,0 +1,68 @@
+use crate::config::ConfigManager;
+use crate::config::parser::is_valid_service_name;
+use crate::i18n::Translator;
+use crate::system::services::{running_services, service_log_path, service_status, start_system_service, stop_system_service};
+use crate::ui::colors::Theme;
+use crate::ui::{display_services, print_info_theme, print_success_theme};
+use crate::utils::fs::get_system_dir;
+
+pub fn handle_service(args: &[String], program: &str, translator: &Translator, theme: &Theme) -> Result<(), Box<dyn std::error::Error>> {
+    let (Some(system_id), Some(action)) = (args.first(), args.get(1)) else {
+        println!("\n  {}\n", theme.error(&translator.t("error_specify_system_id")));
+        println!("  {}\n", theme.info(&translator.t_fmt("usage_service_cmd", &[program])));
+        return Ok(());
+    };
+    let name = args.get(2);
+    if let Some(name) = name.filter(|name| !is_valid_service_name(name)) {
+        println!("\n  {}\n", theme.error(&translator.t_fmt("invalid_service_name", &[name])));
+        return Ok(());
+    }
+
+    match (action.as_str(), name) {
+        ("start", Some(name)) => {
+            let session = start_system_service(system_id, name, translator)?;
+            let log_path = service_log_path(&get_system_dir(system_id)?, name);
+            print_success_theme(&translator.t_fmt("service_started", &[name, &session.pid.to_string()]), theme);
+            print_info_theme(&translator.t_fmt("service_log_hint", &[&log_path.display().to_string()]), theme);
+        }
+        ("stop", Some(name)) => {
+            if stop_system_service(system_id, name, translator)? {
+                print_success_theme(&translator.t_fmt("service_stopped", &[name]), theme);
+            } else {
+                print_info_theme(&translator.t_fmt("service_not_running", &[name]), theme);
+            }
+        }
+        ("status", Some(name)) => {
+            let status = service_status(&get_system_dir(system_id)?, name);
+            display_services(&[(name.clone(), status)], translator);
+        }
+        ("status", None) => {
+            let system_dir = get_system_dir(system_id)?;
+            if !system_dir.exists() {
+                return Err(translator.t_fmt("system_not_exist", &[system_id]).into());
+            }
+
+            let mut names = ConfigManager::new()?.get_services_for_system(system_id)?;
+            for (running, _) in running_services(&system_dir) {
+                if !names.contains(&running) {
+                    names.push(running);
+                }
+            }
+
+            let statuses: Vec<_> = names
+                .into_iter()
+                .map(|name| {
+                    let status = service_status(&system_dir, &name);
+                    (name, status)
+                })
+                .collect();
+            display_services(&statuses, translator);
+        }
+        _ => {
+            println!("\n  {}\n", theme.error(&translator.t_fmt("unknown_argument", &[action])));
+            println!("  {}\n", theme.info(&translator.t_fmt("usage_service_cmd", &[program])));
+        }
+    }
+
+    Ok(())
+}
@@ -1,22 +1,68 @@
 pub fn get_default_config_content() -> &'static str {
-    r#"# 镜像源配置
+    r#"# 镜像源配置：安装时写入系统内的软件源（sources.list、mirrorlist、yum.repos.d、apk/repositories 等）
+# 只替换路径中含有镜像最后一级目录（如 /debian）的条目；pacman 可写完整模板，如 https://example.com/archlinuxarm/$arch/$repo
 ubuntu-mirror = https://mirrors.ustc.edu.cn/ubuntu/
 debian-mirror = https://mirrors.163.com/debian/
 kali-mirror = http://http.kali.org/kali/
 centos-mirror = https://mirrors.aliyun.com/centos/
 fedora-mirror = https://mirrors.tuna.tsinghua.edu.cn/fedora/
 
+# 镜像测速候选（可选）：insOs mirror bench <发行版> 会测试当前镜像和这里列出的镜像，--write 将最快的写入 <发行版>-mirror
+# debian-mirrors = https://mirrors.tuna.tsinghua.edu.cn/debian/, https://mirrors.ustc.edu.cn/debian/
+# 测速时下载的小文件（相对镜像地址，默认为镜像根目录）
+# debian-mirror-probe = dists/stable/Release
+
 # 自定义下载链接配置（可选）
 # ubuntu-link = https://custom-mirror.com/ubuntu-rootfs-arm64.tar.xz
 # debian-link = https://custom-mirror.com/debian-rootfs-arm64.tar.xz
 # kali-link = https://custom-mirror.com/kali-rootfs-arm64.tar.xz
 # centos-link = https://custom-mirror.com/centos-rootfs-arm64.tar.xz
 # fedora-link = https://custom-mirror.com/fedora-rootfs-arm64.tar.xz
+# 按架构指定的链接优先于 <发行版>-link；使用自定义链接时不再校验目录中的校验和
+# debian-link.x86_64 = https://custom-mirror.com/debian-rootfs-x86_64.tar.xz
+
+# GitHub 下载代理（可选）：替换目录中 GitHub 链接的 gh-proxy.org 前缀，设为 none 则直接从 GitHub 下载；
+# 通过代理下载失败时自动改用 GitHub 直链
+# github_proxy = https://ghfast.top/
+# github_proxy = none
+
+# 远程发行版目录（可选）：insOs catalog update 从 catalog_url 下载目录及 <catalog_url>.sig 签名，
+# 使用 catalog_key 中的 OpenSSH 公钥校验（签名命令见 README「发行版目录」）
+# catalog_url = https://example.com/insos/catalog.toml
+# catalog_key = ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAA... insos-catalog
+# 自定义发行版写在 $HOME/termos/catalog.toml 的 [distro.<id>] 表中（见 README「发行版目录」）
 
 # Shell 配置（可选）
 # 自定义登录 shell 命令，默认为 /bin/bash --login
 # shell = /bin/zsh --login
 
+# 容器后端（可选）：proot（默认）、chroot（需要 root）、unshare（Linux 用户命名空间）
+# backend = proot
+# debian1.backend = chroot
+
+# 时区与环境变量（可选）
+# 未配置时区时，安装时自动使用宿主机时区
+# timezone = Asia/Shanghai
+# env.EDITOR = vim
+# 按系统覆盖：<系统ID>.<键>
+# debian1.timezone = UTC
+# debian1.env.LANG = C.UTF-8
+
+# 后台服务（可选）：登录时自动启动，最后一个会话退出时停止
+# 服务命令需在前台运行，未配置命令时直接执行服务名
+# debian1.services = sshd, postgres
+# debian1.service.sshd = /usr/sbin/sshd -D
+# debian1.service.postgres = su postgres -c 'postgres -D /var/lib/postgresql/data'
+
+# 音频（可选）：启动时确保 Termux 中的 PulseAudio 加载 TCP 模块，并设置 PULSE_SERVER
+# debian1.audio = true
+
+# 共享卷（可选）：volume.<名称> = 宿主机目录，首次使用时自动创建
+# <系统ID>.volumes = <名称>[:<系统内路径>]，未指定路径时挂载到 /mnt/<名称>
+# volume.projects = ~/termos/.volumes/projects
+# debian1.volumes = projects:/root/projects
+# archlinux1.volumes = projects
+
 # 自定义初始化命令（可选，支持多行格式）
 # ubuntu-init = ---
 # apt update
@@ -0,0 +1,67 @@
+use crate::utils::net::{probe_url, UrlProbe};
+
+/// Each mirror gets this long to deliver the probe file.
+pub const BENCH_TIMEOUT_SECS: u64 = 5;
+
+#[derive(Debug, Clone)]
+pub struct MirrorBench {
+    pub mirror: String,
+    pub probe: Result<UrlProbe, String>,
+}
+
+/// URL of `probe_path` on `mirror`; an empty path probes the mirror root.
+pub fn probe_url_for(mirror: &str, probe_path: &str) -> String {
+    format!("{}/{}", mirror.trim_end_matches('/'), probe_path.trim_start_matches('/'))
+}
+
+/// Fetches `probe_path` from every mirror in turn and ranks them by
+/// throughput, then latency. Mirrors that failed come last.
+pub fn bench_mirrors(mirrors: &[String], probe_path: &str, timeout_secs: u64) -> Vec<MirrorBench> {
+    let mut results: Vec<MirrorBench> = mirrors
+        .iter()
+        .map(|mirror| MirrorBench {
+            mirror: mirror.clone(),
+            probe: probe_url(&probe_url_for(mirror, probe_path), timeout_secs).map_err(|e| e.to_string()),
+        })
+        .collect();
+
+    results.sort_by(|a, b| match (&a.probe, &b.probe) {
+        (Ok(a), Ok(b)) => b.bytes_per_sec.total_cmp(&a.bytes_per_sec).then(a.latency.cmp(&b.latency)),
+        (Ok(_), Err(_)) => std::cmp::Ordering::Less,
+        (Err(_), Ok(_)) => std::cmp::Ordering::Greater,
+        (Err(_), Err(_)) => std::cmp::Ordering::Equal,
+    });
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_http::{serve, serve_with_delay};
+    use std::time::Duration;
+
+    fn release_file() -> Vec<(String, Vec<u8>)> {
+        vec![("/debian/dists/stable/Release".to_string(), vec![b'x'; 64 * 1024])]
+    }
+
+    #[test]
+    fn test_bench_mirrors_ranks_by_speed() {
+        let slow = format!("{}/debian/", serve_with_delay(release_file(), Duration::from_millis(600)));
+        let fast = format!("{}/debian", serve(release_file()));
+        let broken = format!("{}/missing/", serve(release_file()));
+        let timed_out = format!("{}/debian", serve_with_delay(release_file(), Duration::from_secs(3)));
+
+        let results = bench_mirrors(&[broken.clone(), slow.clone(), timed_out.clone(), fast.clone()], "dists/stable/Release", 2);
+        let ranked: Vec<&str> = results.iter().map(|r| r.mirror.as_str()).collect();
+        assert_eq!(&ranked[..2], &[fast.as_str(), slow.as_str()]);
+        assert!(results[..2].iter().all(|r| r.probe.as_ref().unwrap().bytes == 64 * 1024));
+        assert!(results[1].probe.as_ref().unwrap().latency >= Duration::from_millis(600));
+        assert!(results[2..].iter().all(|r| r.probe.is_err()));
+    }
+
+    #[test]
+    fn test_probe_url_for() {
+        assert_eq!(probe_url_for("https://a/debian/", "/dists/stable/Release"), "https://a/debian/dists/stable/Release");
+        assert_eq!(probe_url_for("https://a/debian", ""), "https://a/debian/");
+    }
+}
@@ -1,5 +1,6 @@
 pub mod parser;
 pub mod defaults;
 pub mod default_config;
+pub mod mirror_bench;
 
 pub use parser::*;
@@ -1,6 +1,6 @@
 use std::fs;
 use std::path::PathBuf;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use crate::utils::fs::get_home_dir;
 use crate::config::defaults::get_default_mirror;
 use crate::config::default_config::get_default_config_content;
@@ -27,8 +27,13 @@ impl ConfigManager {
         Ok(Self { config_dir })
     }
     
+    /// Reads `<dir>/config` without creating defaults; used by tests elsewhere in the crate.
+    #[cfg(test)]
+    pub(crate) fn with_dir(config_dir: &std::path::Path) -> Self {
+        Self { config_dir: config_dir.to_path_buf() }
+    }
+    
     pub fn load_config(&self) -> Result<HashMap<String, String>, Box<dyn std::error::Error>> {
-        let mut config = HashMap::new();
         let config_path = self.config_dir.join("config");
         
         let config_path = if config_path.exists() {
@@ -39,50 +44,10 @@ impl ConfigManager {
         
         if config_path.exists() {
             let content = fs::read_to_string(&config_path)?;
-            let mut lines = content.lines().peekable();
-            
-            while let Some(line) = lines.next() {
-                let trimmed = line.trim();
-                
-                if trimmed.is_empty() || trimmed.starts_with('#') {
-                    continue;
-                }
-                
-                if let Some((key, value)) = trimmed.split_once('=') {
-                    let key = key.trim().to_string();
-                    let value = value.trim();
-                    
-                    if value.starts_with("---") && !value.ends_with("---") {
-                        let mut multi_line_value = String::new();
-                        if value.len() > 3 {
-                            multi_line_value.push_str(&value[3..]);
-                            multi_line_value.push('\n');
-                        }
-                        
-                        while let Some(next_line) = lines.next() {
-                            if next_line.trim().ends_with("---") {
-                                if next_line.len() > 3 {
-                                    multi_line_value.push_str(&next_line[..next_line.len()-3]);
-                                }
-                                break;
-                            } else {
-                                multi_line_value.push_str(next_line);
-                                multi_line_value.push('\n');
-                            }
-                        }
-                        
-                        config.insert(key, multi_line_value.trim().to_string());
-                    } else if value.starts_with("---") && value.ends_with("---") && value.len() >= 6 {
-                        let clean_value = &value[3..value.len()-3];
-                        config.insert(key, clean_value.trim().to_string());
-                    } else {
-                        config.insert(key, value.to_string());
-                    }
-                }
-            }
+            return Ok(parse_config_content(&content));
         }
         
-        Ok(config)
+        Ok(HashMap::new())
     }
     
     pub fn get_mirror_for_distro(&self, distro_name: &str) -> Result<String, Box<dyn std::error::Error>> {
@@ -96,6 +61,51 @@ impl ConfigManager {
         }
     }
     
+    /// Mirrors to benchmark for `distro_name`: the current one followed by
+    /// `<distro>-mirrors` (separated by commas or whitespace), without duplicates.
+    pub fn get_mirror_candidates_for_distro(&self, distro_name: &str) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        let config = self.load_config()?;
+        let list_key = format!("{}-mirrors", distro_name.to_lowercase());
+        
+        let mut candidates: Vec<String> = self.get_mirror_for_distro(distro_name).into_iter().collect();
+        let listed = config.get(&list_key).map(String::as_str).unwrap_or_default();
+        for mirror in listed.split(|c: char| c == ',' || c.is_whitespace()).filter(|m| !m.is_empty()) {
+            if !candidates.iter().any(|c| c.trim_end_matches('/') == mirror.trim_end_matches('/')) {
+                candidates.push(mirror.to_string());
+            }
+        }
+        Ok(candidates)
+    }
+    
+    /// Path fetched from each mirror by `mirror bench`, relative to the mirror;
+    /// `<distro>-mirror-probe`, the mirror root by default.
+    pub fn get_mirror_probe_for_distro(&self, distro_name: &str) -> Result<String, Box<dyn std::error::Error>> {
+        let config = self.load_config()?;
+        let probe_key = format!("{}-mirror-probe", distro_name.to_lowercase());
+        Ok(config.get(&probe_key).cloned().unwrap_or_default())
+    }
+    
+    /// Sets `key` in the config file, replacing the last line that assigns
+    /// it (the one `load_config` reads) or appending a new one. Comments and
+    /// other lines are kept.
+    pub fn set_config_value(&self, key: &str, value: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let config_path = self.config_dir.join("config");
+        let content = fs::read_to_string(&config_path).unwrap_or_default();
+        let assignment = format!("{} = {}", key, value);
+        
+        let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+        let last = lines.iter().rposition(|line| {
+            line.split_once('=').is_some_and(|(k, _)| k.trim() == key) && !line.trim_start().starts_with('#')
+        });
+        match last {
+            Some(index) => lines[index] = assignment,
+            None => lines.push(assignment),
+        }
+        
+        fs::write(&config_path, lines.join("\n") + "\n")?;
+        Ok(())
+    }
+    
     pub fn get_download_link_for_distro(&self, distro_name: &str) -> Result<Option<String>, Box<dyn std::error::Error>> {
         let config = self.load_config()?;
         let link_key = format!("{}-link", distro_name.to_lowercase());
@@ -103,6 +113,24 @@ impl ConfigManager {
         Ok(config.get(&link_key).cloned())
     }
     
+    /// `<distro>-link.<arch>`, falling back to `<distro>-link` for every architecture.
+    pub fn get_download_link_for_arch(&self, distro_name: &str, arch: &str) -> Result<Option<String>, Box<dyn std::error::Error>> {
+        let config = self.load_config()?;
+        let arch_key = format!("{}-link.{}", distro_name.to_lowercase(), arch);
+        
+        match config.get(&arch_key) {
+            Some(link) => Ok(Some(link.clone())),
+            None => self.get_download_link_for_distro(distro_name),
+        }
+    }
+    
+    /// `github_proxy`: `None` keeps catalog URLs as they are, an empty value
+    /// or `none` downloads from GitHub directly.
+    pub fn get_github_proxy(&self) -> Result<Option<String>, Box<dyn std::error::Error>> {
+        let config = self.load_config()?;
+        Ok(config.get("github_proxy").cloned())
+    }
+    
     pub fn get_shell_command(&self) -> Result<Option<String>, Box<dyn std::error::Error>> {
         let config = self.load_config()?;
         Ok(config.get("shell").cloned())
@@ -113,9 +141,125 @@ impl ConfigManager {
         let init_key = format!("{}-init", distro_name.to_lowercase());
         Ok(config.get(&init_key).cloned())
     }
+    
+    /// Looks up `<system_id>.<key>` first and falls back to the global `<key>`.
+    pub fn get_system_setting(&self, system_id: &str, key: &str) -> Result<Option<String>, Box<dyn std::error::Error>> {
+        let config = self.load_config()?;
+        let system_key = format!("{}.{}", system_id, key);
+        Ok(config.get(&system_key).or_else(|| config.get(key)).cloned())
+    }
+    
+    /// Collects `env.NAME` entries, with `<system_id>.env.NAME` overriding the global ones.
+    pub fn get_env_for_system(&self, system_id: &str) -> Result<Vec<(String, String)>, Box<dyn std::error::Error>> {
+        let config = self.load_config()?;
+        let system_prefix = format!("{}.env.", system_id);
+        let mut env = BTreeMap::new();
+        
+        for (key, value) in &config {
+            if let Some(name) = key.strip_prefix("env.") {
+                env.entry(name.to_string()).or_insert_with(|| value.clone());
+            }
+        }
+        for (key, value) in &config {
+            if let Some(name) = key.strip_prefix(&system_prefix) {
+                env.insert(name.to_string(), value.clone());
+            }
+        }
+        
+        Ok(env.into_iter().filter(|(name, _)| !name.is_empty()).collect())
+    }
+    
+    pub fn get_timezone_for_system(&self, system_id: &str) -> Result<Option<String>, Box<dyn std::error::Error>> {
+        self.get_system_setting(system_id, "timezone")
+    }
+    
+    /// Names listed in `services`, separated by commas or whitespace.
+    pub fn get_services_for_system(&self, system_id: &str) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        let services = self.get_system_setting(system_id, "services")?.unwrap_or_default();
+        Ok(services
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|name| is_valid_service_name(name))
+            .map(String::from)
+            .collect())
+    }
+    
+    /// `volume.<name> = <host path>` definitions, sorted by name.
+    pub fn get_volume_definitions(&self) -> Result<Vec<(String, String)>, Box<dyn std::error::Error>> {
+        let config = self.load_config()?;
+        let volumes: BTreeMap<String, String> = config
+            .iter()
+            .filter_map(|(key, value)| Some((key.strip_prefix("volume.")?.to_string(), value.clone())))
+            .filter(|(name, _)| !name.is_empty())
+            .collect();
+        Ok(volumes.into_iter().collect())
+    }
+    
+    pub fn get_service_command(&self, system_id: &str, name: &str) -> Result<Option<String>, Box<dyn std::error::Error>> {
+        if !is_valid_service_name(name) {
+            return Err(format!("无效的服务名: {}", name).into());
+        }
+        self.get_system_setting(system_id, &format!("service.{}", name))
+    }
+}
+
+/// Service names become file names under the system's state directory, so
+/// they must not be empty, contain `/` or start with `.`.
+pub fn is_valid_service_name(name: &str) -> bool {
+    !name.is_empty() && !name.contains('/') && !name.starts_with('.')
+}
+
+pub fn parse_config_content(content: &str) -> HashMap<String, String> {
+    let mut config = HashMap::new();
+    let mut lines = content.lines().peekable();
+    
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+        
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        
+        if let Some((key, value)) = trimmed.split_once('=') {
+            let key = key.trim().to_string();
+            let value = value.trim();
+            
+            // A bare `---` only opens a block; `---text---` is a one-line value.
+            if value.starts_with("---") && (value.len() < 6 || !value.ends_with("---")) {
+                let mut multi_line_value = String::new();
+                if value.len() > 3 {
+                    multi_line_value.push_str(&value[3..]);
+                    multi_line_value.push('\n');
+                }
+                
+                for next_line in lines.by_ref() {
+                    if let Some(last) = next_line.trim_end().strip_suffix("---") {
+                        multi_line_value.push_str(last);
+                        break;
+                    } else {
+                        multi_line_value.push_str(next_line);
+                        multi_line_value.push('\n');
+                    }
+                }
+                
+                config.insert(key, multi_line_value.trim().to_string());
+            } else if value.starts_with("---") && value.ends_with("---") && value.len() >= 6 {
+                let clean_value = &value[3..value.len()-3];
+                config.insert(key, clean_value.trim().to_string());
+            } else {
+                config.insert(key, value.to_string());
+            }
+        }
+    }
+    
+    config
 }
 
 #[cfg(test)]
+#[path = "parser_tests_core.rs"]
 mod parser_tests_core;
 #[cfg(test)]
+#[path = "parser_tests_init.rs"]
 mod parser_tests_init;
+#[cfg(test)]
+#[path = "parser_tests_system.rs"]
+mod parser_tests_system;
@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod tests {
-    use super::*;
-    use std::fs::File;
+    use crate::config::parser::*;
+    use std::fs::{self, File};
     use std::io::Write;
     use tempfile::TempDir;
 
@@ -97,6 +97,56 @@ mod tests {
         assert_eq!(result.unwrap(), Some("https://custom.com/ubuntu.tar.xz".to_string()));
     }
 
+    #[test]
+    fn test_get_download_link_for_arch() {
+        let (_temp_dir, config_manager) = create_temp_config(&[
+            "ubuntu-link = https://custom.com/ubuntu.tar.xz",
+            "ubuntu-link.x86_64 = https://custom.com/ubuntu-x86_64.tar.xz",
+        ]);
+        
+        assert_eq!(
+            config_manager.get_download_link_for_arch("ubuntu", "x86_64").unwrap(),
+            Some("https://custom.com/ubuntu-x86_64.tar.xz".to_string())
+        );
+        assert_eq!(
+            config_manager.get_download_link_for_arch("Ubuntu", "aarch64").unwrap(),
+            Some("https://custom.com/ubuntu.tar.xz".to_string())
+        );
+        assert_eq!(config_manager.get_download_link_for_arch("debian", "aarch64").unwrap(), None);
+    }
+
+    #[test]
+    fn test_get_mirror_candidates_for_distro() {
+        let (_temp_dir, config_manager) = create_temp_config(&[
+            "debian-mirror = https://a.example.com/debian/",
+            "debian-mirrors = https://a.example.com/debian, https://b.example.com/debian/ https://c.example.com/debian/",
+        ]);
+        
+        assert_eq!(config_manager.get_mirror_candidates_for_distro("debian").unwrap(), vec![
+            "https://a.example.com/debian/",
+            "https://b.example.com/debian/",
+            "https://c.example.com/debian/",
+        ]);
+        assert!(config_manager.get_mirror_candidates_for_distro("void").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_set_config_value() {
+        let (temp_dir, config_manager) = create_temp_config(&[
+            "# debian-mirror = https://commented.example.com/",
+            "debian-mirror = https://a.example.com/debian/",
+            "shell = /bin/zsh",
+            "debian-mirror = https://old.example.com/debian/",
+        ]);
+        
+        config_manager.set_config_value("debian-mirror", "https://b.example.com/debian/").unwrap();
+        config_manager.set_config_value("void-mirror", "https://c.example.com/voidlinux/").unwrap();
+        
+        let content = fs::read_to_string(temp_dir.path().join("config")).unwrap();
+        assert_eq!(content, "# debian-mirror = https://commented.example.com/\ndebian-mirror = https://a.example.com/debian/\nshell = /bin/zsh\ndebian-mirror = https://b.example.com/debian/\nvoid-mirror = https://c.example.com/voidlinux/\n");
+        assert_eq!(config_manager.get_mirror_for_distro("debian").unwrap(), "https://b.example.com/debian/");
+    }
+
     #[test]
     fn test_get_download_link_for_distro_without_link() {
         let temp_dir = TempDir::new().unwrap();
@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use crate::config::parser::*;
     use std::fs::File;
     use std::io::Write;
     use tempfile::TempDir;
@@ -0,0 +1,116 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::parser::*;
+    use std::fs::File;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    fn create_temp_config(content: &[&str]) -> (TempDir, ConfigManager) {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config");
+        let mut file = File::create(&config_path).unwrap();
+        for line in content {
+            writeln!(file, "{}", line).unwrap();
+        }
+        let config_manager = ConfigManager {
+            config_dir: temp_dir.path().to_path_buf(),
+        };
+        (temp_dir, config_manager)
+    }
+
+    #[test]
+    fn test_get_system_setting_prefers_system_key() {
+        let (_temp_dir, config_manager) = create_temp_config(&[
+            "timezone = UTC",
+            "debian1.timezone = Asia/Shanghai",
+        ]);
+        
+        assert_eq!(config_manager.get_system_setting("debian1", "timezone").unwrap(), Some("Asia/Shanghai".to_string()));
+        assert_eq!(config_manager.get_system_setting("arch1", "timezone").unwrap(), Some("UTC".to_string()));
+        assert_eq!(config_manager.get_system_setting("arch1", "missing").unwrap(), None);
+    }
+
+    #[test]
+    fn test_get_env_for_system_merges_global_and_system() {
+        let (_temp_dir, config_manager) = create_temp_config(&[
+            "env.EDITOR = vim",
+            "env.LANG = en_US.UTF-8",
+            "debian1.env.LANG = C.UTF-8",
+            "arch1.env.PAGER = less",
+        ]);
+        
+        let env = config_manager.get_env_for_system("debian1").unwrap();
+        assert_eq!(env, vec![
+            ("EDITOR".to_string(), "vim".to_string()),
+            ("LANG".to_string(), "C.UTF-8".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_get_env_for_system_without_entries() {
+        let (_temp_dir, config_manager) = create_temp_config(&["shell = /bin/zsh"]);
+        
+        let env = config_manager.get_env_for_system("debian1").unwrap();
+        assert!(env.is_empty());
+    }
+
+    #[test]
+    fn test_parse_config_content() {
+        let config = parse_config_content("# comment\nkey = value\n\nother=1\n");
+        assert_eq!(config.get("key"), Some(&"value".to_string()));
+        assert_eq!(config.get("other"), Some(&"1".to_string()));
+        assert_eq!(config.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_config_content_multi_line() {
+        let config = parse_config_content("a = ---first\nmiddle\nlast---  \nb = ---one line---\nc = ---\nonly\n---\n");
+        assert_eq!(config.get("a"), Some(&"first\nmiddle\nlast".to_string()));
+        assert_eq!(config.get("b"), Some(&"one line".to_string()));
+        assert_eq!(config.get("c"), Some(&"only".to_string()));
+    }
+
+    #[test]
+    fn test_get_services_for_system() {
+        let (_temp_dir, config_manager) = create_temp_config(&[
+            "services = cron",
+            "debian1.services = sshd, postgres  redis",
+            "debian1.service.sshd = /usr/sbin/sshd -D",
+            "service.cron = cron -f",
+        ]);
+        
+        assert_eq!(config_manager.get_services_for_system("debian1").unwrap(), vec!["sshd", "postgres", "redis"]);
+        assert_eq!(config_manager.get_services_for_system("arch1").unwrap(), vec!["cron"]);
+        assert_eq!(config_manager.get_service_command("debian1", "sshd").unwrap(), Some("/usr/sbin/sshd -D".to_string()));
+        assert_eq!(config_manager.get_service_command("debian1", "cron").unwrap(), Some("cron -f".to_string()));
+        assert_eq!(config_manager.get_service_command("debian1", "redis").unwrap(), None);
+        assert!(config_manager.get_service_command("debian1", "../../meta.toml").is_err());
+    }
+
+    #[test]
+    fn test_is_valid_service_name() {
+        assert!(is_valid_service_name("sshd"));
+        assert!(is_valid_service_name("my-service.v2"));
+        assert!(!is_valid_service_name(""));
+        assert!(!is_valid_service_name("../../meta.toml"));
+        assert!(!is_valid_service_name("a/b"));
+        assert!(!is_valid_service_name(".hidden"));
+        
+        let (_temp_dir, config_manager) = create_temp_config(&["services = sshd ../meta.toml .x"]);
+        assert_eq!(config_manager.get_services_for_system("debian1").unwrap(), vec!["sshd"]);
+    }
+
+    #[test]
+    fn test_get_volume_definitions() {
+        let (_temp_dir, config_manager) = create_temp_config(&[
+            "volume.projects = ~/termos/.volumes/projects",
+            "volume.data = /sdcard/data",
+            "debian1.volumes = projects",
+        ]);
+        
+        assert_eq!(config_manager.get_volume_definitions().unwrap(), vec![
+            ("data".to_string(), "/sdcard/data".to_string()),
+            ("projects".to_string(), "~/termos/.volumes/projects".to_string()),
+        ]);
+    }
+}
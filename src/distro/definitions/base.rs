@@ -1,18 +1,13 @@
-use std::collections::HashMap;
+use std::path::Path;
 use crate::utils::arch::Architecture;
-use crate::distro::name::DistroName;
+use crate::distro::package_manager::PackageManager;
+use crate::distro::definitions::catalog::{base_catalog, load_catalog};
 use crate::distro::definitions::distro_definition::DistroDefinition;
 
+/// The base catalog with the user's catalog applied. An invalid user
+/// catalog is ignored here; commands report it through [`load_catalog`].
 pub fn get_all_distros() -> Vec<DistroDefinition> {
-    let mut distros = Vec::new();
-    
-    distros.extend(crate::distro::definitions::adelie::get_definition());
-    distros.extend(crate::distro::definitions::debian::get_definitions());
-    distros.extend(crate::distro::definitions::arch::get_definitions());
-    distros.extend(crate::distro::definitions::redhat::get_definitions());
-    distros.extend(crate::distro::definitions::others::get_definitions());
-    
-    distros
+    load_catalog().unwrap_or_else(|_| base_catalog().1)
 }
 
 pub fn get_distro_by_name(name: &str) -> Option<DistroDefinition> {
@@ -20,6 +15,12 @@ pub fn get_distro_by_name(name: &str) -> Option<DistroDefinition> {
     all_distros.into_iter().find(|d| d.name.as_str() == name.to_lowercase())
 }
 
+/// The package manager found in `rootfs`, or else the one the catalog
+/// declares for `distro`.
+pub fn package_manager_for(rootfs: &Path, distro: &str) -> Option<PackageManager> {
+    PackageManager::detect(rootfs).or_else(|| get_distro_by_name(distro)?.package_manager)
+}
+
 pub fn get_distros_for_arch(arch: &Architecture) -> Vec<DistroDefinition> {
     let all_distros = get_all_distros();
     all_distros.into_iter()
@@ -27,6 +28,19 @@ pub fn get_distros_for_arch(arch: &Architecture) -> Vec<DistroDefinition> {
         .collect()
 }
 
+/// Distros that cannot run natively on `host` but do ship a rootfs for one of
+/// the `emulated` architectures, paired with the architecture to install.
+pub fn get_emulated_distros(host: &Architecture, emulated: &[Architecture]) -> Vec<(DistroDefinition, Architecture)> {
+    get_all_distros()
+        .into_iter()
+        .filter(|d| !Architecture::all().iter().any(|arch| host.can_run_natively(arch) && d.supports_arch(arch)))
+        .filter_map(|d| {
+            let arch = emulated.iter().find(|arch| d.supports_arch(arch)).copied()?;
+            Some((d, arch))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -48,6 +62,23 @@ mod tests {
         assert!(distro.is_none());
     }
 
+    #[test]
+    fn test_builtin_releases() {
+        let bookworm = get_distro_by_name("debian").unwrap().with_release("bookworm").unwrap();
+        assert_eq!(bookworm.release.as_deref(), Some("bookworm"));
+        for arch in [Architecture::Aarch64, Architecture::Arm, Architecture::X86_64, Architecture::I686] {
+            assert!(bookworm.get_url(&arch).unwrap().contains(&format!("debian-bookworm-{}", arch.to_str())));
+        }
+
+        let ubuntu = get_distro_by_name("ubuntu").unwrap();
+        assert_eq!(ubuntu.release.as_deref(), Some("questing"));
+        assert!(ubuntu.with_release("noble").unwrap().get_url(&Architecture::Aarch64).unwrap().contains("ubuntu-noble-aarch64"));
+
+        let alpine = get_distro_by_name("alpine").unwrap();
+        assert_eq!(alpine.release.as_deref(), Some("stable"));
+        assert!(alpine.with_release("edge").unwrap().get_url(&Architecture::Riscv64).unwrap().contains("/edge/"));
+    }
+
     #[test]
     fn test_get_distros_for_arch() {
         let aarch64_distros = get_distros_for_arch(&Architecture::Aarch64);
@@ -57,4 +88,18 @@ mod tests {
             assert!(distro.supports_arch(&Architecture::Aarch64));
         }
     }
+
+    #[test]
+    fn test_get_emulated_distros() {
+        let emulated = get_emulated_distros(&Architecture::Aarch64, &[Architecture::X86_64]);
+        assert!(emulated.is_empty());
+        
+        let emulated = get_emulated_distros(&Architecture::I686, &[Architecture::Aarch64, Architecture::X86_64]);
+        assert!(!emulated.is_empty());
+        for (distro, arch) in &emulated {
+            assert!(!distro.supports_arch(&Architecture::I686));
+            assert!(distro.supports_arch(arch));
+        }
+        assert!(emulated.iter().any(|(d, arch)| d.name.as_str() == "fedora" && *arch == Architecture::Aarch64));
+    }
 }
@@ -0,0 +1,80 @@
+use std::fs;
+use std::path::PathBuf;
+use crate::distro::definitions::distro_definition::DistroDefinition;
+use crate::utils::fs::get_home_dir;
+
+pub use super::catalog_fields::normalize_sha256;
+pub use super::catalog_merge::merge_entries;
+pub use super::catalog_parse::parse_catalog;
+pub use super::catalog_render::{append_catalog_entry, render_entry};
+pub use super::catalog_source::CatalogError;
+
+pub const CATALOG_SCHEMA_VERSION: i64 = 1;
+pub const CATALOG_FILE: &str = "catalog.toml";
+pub const CACHED_CATALOG_DIR: &str = ".cache";
+
+const BUILTIN_CATALOG: &str = include_str!("catalog.toml");
+
+/// Definitions of a complete catalog, as opposed to a set of overrides.
+pub fn catalog_definitions(content: &str, file: &str) -> Result<(u64, Vec<DistroDefinition>), CatalogError> {
+    let catalog = parse_catalog(content, file)?;
+    let mut distros = Vec::new();
+    merge_entries(&mut distros, catalog.entries, file)?;
+    Ok((catalog.serial, distros))
+}
+
+fn parse_builtin_catalog() -> (u64, Vec<DistroDefinition>) {
+    catalog_definitions(BUILTIN_CATALOG, "<builtin>").expect("built-in catalog is invalid")
+}
+
+/// The catalog compiled into the binary; its validity is checked by tests.
+#[cfg(test)]
+pub fn builtin_catalog() -> Vec<DistroDefinition> {
+    parse_builtin_catalog().1
+}
+
+pub fn user_catalog_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    Ok(get_home_dir()?.join("termos").join(CATALOG_FILE))
+}
+
+/// Where `insOs catalog update` keeps the last verified remote catalog.
+pub fn cached_catalog_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    Ok(get_home_dir()?.join("termos").join(CACHED_CATALOG_DIR).join(CATALOG_FILE))
+}
+
+/// Serial and definitions of the newer of the built-in and the cached
+/// remote catalog. A cache that no longer parses is skipped.
+pub fn base_catalog() -> (u64, Vec<DistroDefinition>) {
+    let builtin = parse_builtin_catalog();
+    let cached = cached_catalog_path()
+        .ok()
+        .and_then(|path| fs::read_to_string(&path).ok().map(|content| (path, content)))
+        .and_then(|(path, content)| catalog_definitions(&content, &path.to_string_lossy()).ok());
+
+    match cached {
+        Some(cached) if cached.0 > builtin.0 => cached,
+        _ => builtin,
+    }
+}
+
+/// The base catalog with `$HOME/termos/catalog.toml` applied, if any.
+pub fn load_catalog() -> Result<Vec<DistroDefinition>, Box<dyn std::error::Error>> {
+    let (_, mut distros) = base_catalog();
+    let path = user_catalog_path()?;
+    if path.exists() {
+        let file = path.to_string_lossy();
+        let catalog = parse_catalog(&fs::read_to_string(&path)?, &file)?;
+        merge_entries(&mut distros, catalog.entries, &file)?;
+    }
+    Ok(distros)
+}
+
+#[cfg(test)]
+#[path = "catalog_tests_core.rs"]
+mod catalog_tests_core;
+#[cfg(test)]
+#[path = "catalog_tests_release.rs"]
+mod catalog_tests_release;
+#[cfg(test)]
+#[path = "catalog_tests_format.rs"]
+mod catalog_tests_format;
@@ -0,0 +1,92 @@
+use crate::distro::definitions::catalog_fields::{arch_table, normalize_sha256, parse_url, string_field, uint_field};
+use crate::distro::definitions::catalog_parse::{CatalogEntry, CatalogRelease};
+use crate::distro::definitions::catalog_source::{locate, CatalogError, Source};
+use crate::distro::package_manager::PackageManager;
+use crate::distro::name::DistroName;
+
+const ENTRY_KEYS: [&str; 10] = [
+    "display_name", "description", "default_packages", "urls", "checksums", "strip_components",
+    "package_manager", "releases", "default_release", "installed_size_mib",
+];
+const RELEASE_KEYS: [&str; 2] = ["urls", "checksums"];
+
+/// One `[distro.<id>]` table, with its releases.
+pub(super) fn parse_entry(source: &Source, id: &str, value: &toml::Value) -> Result<CatalogEntry, CatalogError> {
+    let Some(table) = value.as_table() else {
+        return Err(source.error(&["distro", id], format!("distro.{} 应为表", id)));
+    };
+    let name = DistroName::from_str(id)
+        .ok_or_else(|| source.error(&["distro", id], format!("无效的发行版标识: {}", id)))?;
+    if let Some(key) = table.keys().find(|key| !ENTRY_KEYS.contains(&key.as_str())) {
+        return Err(source.error(&["distro", id, key], format!("未知字段 {}", key)));
+    }
+
+    let default_packages = match table.get("default_packages") {
+        Some(toml::Value::Array(values)) => Some(
+            values
+                .iter()
+                .map(|value| value.as_str().map(str::to_string))
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| source.error(&["distro", id, "default_packages"], "字段 default_packages 的类型应为字符串数组".to_string()))?,
+        ),
+        Some(_) => return Err(source.error(&["distro", id, "default_packages"], "字段 default_packages 的类型应为字符串数组".to_string())),
+        None => None,
+    };
+
+    let strip_components = uint_field(source, id, table, "strip_components")?;
+    let installed_size_mib = uint_field(source, id, table, "installed_size_mib")?;
+
+    let package_manager = match string_field(source, id, table, "package_manager")? {
+        Some(value) => Some(
+            PackageManager::from_str(&value)
+                .ok_or_else(|| source.error(&["distro", id, "package_manager"], format!("未知的包管理器: {}", value)))?,
+        ),
+        None => None,
+    };
+
+    let mut releases = Vec::new();
+    match table.get("releases") {
+        Some(toml::Value::Table(values)) => {
+            for (release, value) in values {
+                releases.push(parse_release(source, id, release, value)?);
+            }
+        }
+        Some(_) => return Err(source.error(&["distro", id, "releases"], "字段 releases 的类型应为表".to_string())),
+        None => {}
+    }
+    releases.sort_by_key(|release| release.line);
+
+    let path = ["distro", id];
+    Ok(CatalogEntry {
+        name,
+        line: locate(source.content, &path),
+        display_name: string_field(source, id, table, "display_name")?,
+        description: string_field(source, id, table, "description")?,
+        default_packages,
+        urls: arch_table(source, &path, table, "urls", parse_url)?,
+        checksums: arch_table(source, &path, table, "checksums", normalize_sha256)?,
+        strip_components,
+        package_manager,
+        releases,
+        default_release: string_field(source, id, table, "default_release")?,
+        installed_size_mib,
+    })
+}
+
+fn parse_release(source: &Source, id: &str, name: &str, value: &toml::Value) -> Result<CatalogRelease, CatalogError> {
+    let path = ["distro", id, "releases", name];
+    let Some(table) = value.as_table() else {
+        return Err(source.error(&path, format!("distro.{}.releases.{} 应为表", id, name)));
+    };
+    if let Some(key) = table.keys().find(|key| !RELEASE_KEYS.contains(&key.as_str())) {
+        let key_path = ["distro", id, "releases", name, key.as_str()];
+        return Err(source.error(&key_path, format!("未知字段 {}", key)));
+    }
+
+    Ok(CatalogRelease {
+        name: name.to_string(),
+        line: locate(source.content, &path),
+        urls: arch_table(source, &path, table, "urls", parse_url)?,
+        checksums: arch_table(source, &path, table, "checksums", normalize_sha256)?,
+    })
+}
@@ -0,0 +1,60 @@
+use crate::distro::definitions::catalog_source::{CatalogError, Source};
+use crate::utils::arch::Architecture;
+
+pub(super) fn string_field(source: &Source, id: &str, table: &toml::Table, key: &str) -> Result<Option<String>, CatalogError> {
+    match table.get(key) {
+        Some(toml::Value::String(value)) => Ok(Some(value.clone())),
+        Some(_) => Err(source.error(&["distro", id, key], format!("字段 {} 的类型应为字符串", key))),
+        None => Ok(None),
+    }
+}
+
+/// A non-negative integer field such as `strip_components`.
+pub(super) fn uint_field<T: TryFrom<i64>>(source: &Source, id: &str, table: &toml::Table, key: &str) -> Result<Option<T>, CatalogError> {
+    let error = || source.error(&["distro", id, key], format!("字段 {} 的类型应为非负整数", key));
+    match table.get(key) {
+        Some(toml::Value::Integer(value)) => T::try_from(*value).map(Some).map_err(|_| error()),
+        Some(_) => Err(error()),
+        None => Ok(None),
+    }
+}
+
+pub(super) fn parse_url(url: &str) -> Option<String> {
+    url.contains("://").then(|| url.to_string())
+}
+
+/// Lowercase hex of a SHA-256 given as hex, optionally prefixed `sha256:`.
+pub fn normalize_sha256(value: &str) -> Option<String> {
+    let hex = value.strip_prefix("sha256:").unwrap_or(value).to_lowercase();
+    (hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit())).then_some(hex)
+}
+
+/// A table keyed by architecture, such as `urls` or `checksums`, whose
+/// values `parse` validates.
+pub(super) fn arch_table(
+    source: &Source,
+    table_path: &[&str],
+    table: &toml::Table,
+    key: &str,
+    parse: impl Fn(&str) -> Option<String>,
+) -> Result<Vec<(Architecture, String)>, CatalogError> {
+    let key_path: Vec<&str> = table_path.iter().copied().chain([key]).collect();
+    let values = match table.get(key) {
+        Some(toml::Value::Table(values)) => values,
+        Some(_) => return Err(source.error(&key_path, format!("字段 {} 的类型应为表", key))),
+        None => return Ok(Vec::new()),
+    };
+
+    let mut result = Vec::new();
+    for (arch_name, value) in values {
+        let path: Vec<&str> = key_path.iter().copied().chain([arch_name.as_str()]).collect();
+        let arch = Architecture::from_str(arch_name)
+            .ok_or_else(|| source.error(&path, format!("未知的架构: {}", arch_name)))?;
+        let value = value
+            .as_str()
+            .and_then(&parse)
+            .ok_or_else(|| source.error(&path, format!("{}.{} 的值无效", key, arch_name)))?;
+        result.push((arch, value));
+    }
+    Ok(result)
+}
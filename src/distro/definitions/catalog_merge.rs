@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+use crate::distro::definitions::catalog_parse::CatalogEntry;
+use crate::distro::definitions::catalog_source::CatalogError;
+use crate::distro::definitions::distro_definition::{DistroDefinition, DistroRelease};
+use crate::utils::arch::Architecture;
+
+fn merge_arch_values(
+    urls: &mut HashMap<Architecture, String>,
+    checksums: &mut HashMap<Architecture, String>,
+    new_urls: Vec<(Architecture, String)>,
+    new_checksums: Vec<(Architecture, String)>,
+) {
+    for (arch, _) in &new_urls {
+        checksums.remove(arch);
+    }
+    urls.extend(new_urls);
+    checksums.extend(new_checksums);
+}
+
+fn apply_entry(distro: &mut DistroDefinition, entry: CatalogEntry, file: &str) -> Result<(), CatalogError> {
+    let error = |line: usize, message: String| CatalogError { file: file.to_string(), line, message };
+
+    if let Some(display_name) = entry.display_name {
+        distro.display_name = display_name;
+    }
+    if let Some(description) = entry.description {
+        distro.description = description;
+    }
+    if let Some(packages) = entry.default_packages {
+        distro.default_packages = packages;
+    }
+    if let Some(strip_components) = entry.strip_components {
+        distro.strip_components = strip_components;
+    }
+    if entry.package_manager.is_some() {
+        distro.package_manager = entry.package_manager;
+    }
+    if entry.installed_size_mib.is_some() {
+        distro.installed_size_mib = entry.installed_size_mib;
+    }
+
+    for release in entry.releases {
+        match distro.releases.iter_mut().find(|r| r.name == release.name) {
+            Some(existing) => merge_arch_values(&mut existing.urls, &mut existing.checksums, release.urls, release.checksums),
+            None if release.urls.is_empty() => {
+                return Err(error(release.line, format!("发行版本 {} 缺少字段 urls", release.name)));
+            }
+            None => distro.releases.push(DistroRelease {
+                name: release.name,
+                urls: release.urls.into_iter().collect(),
+                checksums: release.checksums.into_iter().collect(),
+            }),
+        }
+    }
+    if let Some(default_release) = entry.default_release {
+        if distro.get_release(&default_release).is_none() {
+            return Err(error(entry.line, format!("default_release 指向不存在的发行版本: {}", default_release)));
+        }
+        distro.release = Some(default_release);
+    }
+    if distro.release.is_none() && distro.urls.is_empty() {
+        distro.release = distro.releases.first().map(|release| release.name.clone());
+    }
+
+    // Top-level URLs belong to the default release, if it is a named one.
+    let (urls, checksums) = match distro.release.clone() {
+        Some(name) => {
+            let release = distro.releases.iter_mut().find(|r| r.name == name).expect("default release exists");
+            (&mut release.urls, &mut release.checksums)
+        }
+        None => (&mut distro.urls, &mut distro.checksums),
+    };
+    merge_arch_values(urls, checksums, entry.urls, entry.checksums);
+    if let Some(release) = distro.release.clone().and_then(|name| distro.with_release(&name)) {
+        *distro = release;
+    }
+    Ok(())
+}
+
+/// Applies `entries` on top of `distros`: fields an entry sets replace the
+/// existing ones and its URLs and checksums are merged per architecture and
+/// release; top-level `urls` belong to the default release. A replaced URL
+/// drops the old checksum unless the entry brings a new one. A distro that
+/// is not in `distros` yet needs a display name and a URL.
+pub fn merge_entries(distros: &mut Vec<DistroDefinition>, entries: Vec<CatalogEntry>, file: &str) -> Result<(), CatalogError> {
+    for entry in entries {
+        if let Some(distro) = distros.iter_mut().find(|d| d.name == entry.name) {
+            apply_entry(distro, entry, file)?;
+            continue;
+        }
+
+        let line = entry.line;
+        let error = |message: String| CatalogError { file: file.to_string(), line, message };
+        let id = entry.name.as_str().to_string();
+        let mut distro = DistroDefinition {
+            name: entry.name.clone(),
+            display_name: entry.display_name.clone().ok_or_else(|| error(format!("{} 缺少字段 display_name", id)))?,
+            urls: HashMap::new(),
+            description: String::new(),
+            default_packages: Vec::new(),
+            checksums: HashMap::new(),
+            strip_components: 0,
+            package_manager: None,
+            releases: Vec::new(),
+            release: None,
+            installed_size_mib: None,
+        };
+        apply_entry(&mut distro, entry, file)?;
+        if distro.urls.is_empty() {
+            return Err(error(format!("{} 缺少字段 urls", id)));
+        }
+        distros.push(distro);
+    }
+    Ok(())
+}
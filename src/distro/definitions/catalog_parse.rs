@@ -0,0 +1,82 @@
+use crate::distro::definitions::catalog::CATALOG_SCHEMA_VERSION;
+use crate::distro::definitions::catalog_entry::parse_entry;
+use crate::distro::definitions::catalog_source::{line_of, CatalogError, Source};
+use crate::distro::package_manager::PackageManager;
+use crate::distro::name::DistroName;
+use crate::utils::arch::Architecture;
+
+/// A parsed catalog file. `serial` orders catalogs from different sources so
+/// that an older download never replaces a newer built-in catalog; files
+/// without one have serial 0.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Catalog {
+    pub serial: u64,
+    pub entries: Vec<CatalogEntry>,
+}
+
+/// One `[distro.<id>]` table. Everything but the id is optional so that a
+/// user catalog can override part of a built-in entry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatalogEntry {
+    pub name: DistroName,
+    pub line: usize,
+    pub display_name: Option<String>,
+    pub description: Option<String>,
+    pub default_packages: Option<Vec<String>>,
+    pub urls: Vec<(Architecture, String)>,
+    pub checksums: Vec<(Architecture, String)>,
+    pub strip_components: Option<u32>,
+    pub package_manager: Option<PackageManager>,
+    pub releases: Vec<CatalogRelease>,
+    pub default_release: Option<String>,
+    pub installed_size_mib: Option<u64>,
+}
+
+/// A `[distro.<id>.releases.<name>]` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatalogRelease {
+    pub name: String,
+    pub line: usize,
+    pub urls: Vec<(Architecture, String)>,
+    pub checksums: Vec<(Architecture, String)>,
+}
+
+/// Parses and validates a catalog, keeping its entries in file order.
+/// `file` is only used in error messages.
+pub fn parse_catalog(content: &str, file: &str) -> Result<Catalog, CatalogError> {
+    let source = Source { file, content };
+    let table: toml::Table = content.parse().map_err(|e: toml::de::Error| CatalogError {
+        file: file.to_string(),
+        line: e.span().map(|span| line_of(content, span.start)).unwrap_or(1),
+        message: e.message().to_string(),
+    })?;
+
+    if let Some(key) = table.keys().find(|key| !matches!(key.as_str(), "schema_version" | "serial" | "distro")) {
+        return Err(source.error(&[key], format!("未知字段 {}", key)));
+    }
+    match table.get("schema_version") {
+        Some(toml::Value::Integer(version)) if (1..=CATALOG_SCHEMA_VERSION).contains(version) => {}
+        Some(toml::Value::Integer(version)) => {
+            return Err(source.error(&["schema_version"], format!("不支持的目录版本: {} (当前版本 {})", version, CATALOG_SCHEMA_VERSION)));
+        }
+        Some(_) => return Err(source.error(&["schema_version"], "字段 schema_version 的类型应为整数".to_string())),
+        None => {}
+    }
+    let serial = match table.get("serial") {
+        Some(toml::Value::Integer(serial)) => u64::try_from(*serial)
+            .map_err(|_| source.error(&["serial"], "字段 serial 的类型应为非负整数".to_string()))?,
+        Some(_) => return Err(source.error(&["serial"], "字段 serial 的类型应为非负整数".to_string())),
+        None => 0,
+    };
+
+    let mut entries = match table.get("distro") {
+        Some(toml::Value::Table(distros)) => distros
+            .iter()
+            .map(|(id, value)| parse_entry(&source, id, value))
+            .collect::<Result<Vec<_>, _>>()?,
+        Some(_) => return Err(source.error(&["distro"], "字段 distro 的类型应为表".to_string())),
+        None => Vec::new(),
+    };
+    entries.sort_by_key(|entry| entry.line);
+    Ok(Catalog { serial, entries })
+}
@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use crate::distro::definitions::catalog::CATALOG_SCHEMA_VERSION;
+use crate::distro::definitions::catalog_parse::parse_catalog;
+use crate::distro::definitions::distro_definition::DistroDefinition;
+use crate::utils::arch::Architecture;
+
+fn quote(value: &str) -> String {
+    toml::Value::String(value.to_string()).to_string()
+}
+
+/// `[distro.<id>]` tables describing `distro`, in the catalog format.
+pub fn render_entry(distro: &DistroDefinition) -> String {
+    let id = distro.name.as_str();
+    let mut result = format!("[distro.{}]\n", id);
+    result.push_str(&format!("display_name = {}\n", quote(&distro.display_name)));
+    if !distro.description.is_empty() {
+        result.push_str(&format!("description = {}\n", quote(&distro.description)));
+    }
+    if !distro.default_packages.is_empty() {
+        let packages: Vec<String> = distro.default_packages.iter().map(|p| quote(p)).collect();
+        result.push_str(&format!("default_packages = [{}]\n", packages.join(", ")));
+    }
+    if distro.strip_components > 0 {
+        result.push_str(&format!("strip_components = {}\n", distro.strip_components));
+    }
+    if let Some(manager) = distro.package_manager {
+        result.push_str(&format!("package_manager = {}\n", quote(manager.as_str())));
+    }
+    if let Some(size) = distro.installed_size_mib {
+        result.push_str(&format!("installed_size_mib = {}\n", size));
+    }
+    if let Some(release) = &distro.release {
+        result.push_str(&format!("default_release = {}\n", quote(release)));
+    } else {
+        render_arch_tables(&mut result, &format!("distro.{}", id), &distro.urls, &distro.checksums);
+    }
+    // The top-level URLs and checksums are those of the default release.
+    for release in &distro.releases {
+        let table = format!("distro.{}.releases.{}", id, quote(&release.name));
+        if distro.release.as_ref() == Some(&release.name) {
+            render_arch_tables(&mut result, &table, &distro.urls, &distro.checksums);
+        } else {
+            render_arch_tables(&mut result, &table, &release.urls, &release.checksums);
+        }
+    }
+    result
+}
+
+fn render_arch_tables(result: &mut String, table: &str, urls: &HashMap<Architecture, String>, checksums: &HashMap<Architecture, String>) {
+    for (key, values) in [("urls", urls), ("checksums", checksums)] {
+        if values.is_empty() {
+            continue;
+        }
+        result.push_str(&format!("\n[{}.{}]\n", table, key));
+        for arch in Architecture::all() {
+            if let Some(value) = values.get(&arch) {
+                result.push_str(&format!("{} = {}\n", arch.to_str(), quote(value)));
+            }
+        }
+    }
+}
+
+/// Appends `distro` to the catalog at `path`, creating it if needed.
+/// Returns `false` without writing if the catalog already has an entry for it.
+pub fn append_catalog_entry(path: &Path, distro: &DistroDefinition) -> Result<bool, Box<dyn std::error::Error>> {
+    let file = path.to_string_lossy();
+    let mut content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => format!("schema_version = {}\n", CATALOG_SCHEMA_VERSION),
+        Err(e) => return Err(e.into()),
+    };
+    if parse_catalog(&content, &file)?.entries.iter().any(|entry| entry.name == distro.name) {
+        return Ok(false);
+    }
+
+    if !content.ends_with('\n') {
+        content.push('\n');
+    }
+    content.push('\n');
+    content.push_str(&render_entry(distro));
+    parse_catalog(&content, &file)?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, content)?;
+    Ok(true)
+}
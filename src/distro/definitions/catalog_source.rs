@@ -0,0 +1,67 @@
+use std::fmt;
+
+/// A problem in a catalog file and the 1-based line it was found on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatalogError {
+    pub file: String,
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for CatalogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.file, self.line, self.message)
+    }
+}
+
+impl std::error::Error for CatalogError {}
+
+/// The file being parsed, for locating errors.
+pub(super) struct Source<'a> {
+    pub(super) file: &'a str,
+    pub(super) content: &'a str,
+}
+
+impl Source<'_> {
+    pub(super) fn error(&self, path: &[&str], message: String) -> CatalogError {
+        CatalogError {
+            file: self.file.to_string(),
+            line: locate(self.content, path),
+            message,
+        }
+    }
+}
+
+pub(super) fn line_of(content: &str, offset: usize) -> usize {
+    content[..offset.min(content.len())].matches('\n').count() + 1
+}
+
+fn key_path(raw: &str) -> Vec<String> {
+    raw.split('.')
+        .map(|part| part.trim().trim_matches('"').trim_matches('\'').to_string())
+        .collect()
+}
+
+/// Line of the first table header or key whose dotted path starts with
+/// `path`, falling back to its parent when `path` was never written.
+pub(super) fn locate(content: &str, path: &[&str]) -> usize {
+    let mut table: Vec<String> = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        let full = if let Some(header) = line.strip_prefix('[') {
+            table = key_path(header.split(']').next().unwrap_or_default());
+            table.clone()
+        } else if let Some((key, _)) = line.split_once('=').filter(|_| !line.starts_with('#')) {
+            table.iter().cloned().chain(key_path(key)).collect()
+        } else {
+            continue;
+        };
+        if full.len() >= path.len() && full.iter().zip(path).all(|(a, b)| a == b) {
+            return index + 1;
+        }
+    }
+    match path.split_last() {
+        Some((_, parent)) if !parent.is_empty() => locate(content, parent),
+        _ => 1,
+    }
+}
@@ -0,0 +1,106 @@
+#[cfg(test)]
+mod tests {
+    use crate::distro::definitions::catalog::*;
+    use crate::distro::definitions::distro_definition::DistroDefinition;
+    use crate::distro::name::DistroName;
+    use crate::distro::package_manager::PackageManager;
+    use crate::utils::arch::Architecture;
+
+    fn find(distros: &[DistroDefinition], name: DistroName) -> &DistroDefinition {
+        distros.iter().find(|d| d.name == name).unwrap()
+    }
+
+    #[test]
+    fn test_builtin_catalog() {
+        let (serial, distros) = parse_builtin_catalog();
+        assert!(serial > 0);
+        assert_eq!(distros.len(), DistroName::all().len());
+        assert_eq!(distros[0].name, DistroName::Adelie);
+        assert!(distros.iter().all(|d| !d.urls.is_empty() && !d.description.is_empty()));
+    }
+
+    #[test]
+    fn test_builtin_catalog_architectures() {
+        let distros = builtin_catalog();
+        let supported = |name: DistroName| -> Vec<Architecture> {
+            Architecture::all().into_iter().filter(|arch| find(&distros, name.clone()).supports_arch(arch)).collect()
+        };
+
+        use Architecture::*;
+        assert_eq!(supported(DistroName::Debian), vec![Aarch64, Arm, X86_64, I686]);
+        assert_eq!(supported(DistroName::Ubuntu), vec![Aarch64, Arm, X86_64]);
+        assert_eq!(supported(DistroName::Archlinux), vec![Aarch64, Arm, X86_64, I686]);
+        assert_eq!(supported(DistroName::Fedora), vec![Aarch64, X86_64]);
+        assert_eq!(supported(DistroName::Rockylinux), vec![Aarch64, X86_64]);
+        assert_eq!(supported(DistroName::Alpine), vec![Aarch64, Arm, X86_64, I686, Riscv64]);
+        assert_eq!(supported(DistroName::Opensuse), vec![Aarch64, Arm, X86_64, I686]);
+    }
+
+    #[test]
+    fn test_user_catalog_overrides_and_extends() {
+        let content = r#"
+[distro.ubuntu]
+description = "Ubuntu from a local mirror"
+
+[distro.ubuntu.urls]
+x86_64 = "https://mirror.example.com/ubuntu-x86_64.tar.xz"
+riscv64 = "https://mirror.example.com/ubuntu-riscv64.tar.xz"
+"#;
+        let mut distros = builtin_catalog();
+        merge_entries(&mut distros, parse_catalog(content, "catalog.toml").unwrap().entries, "catalog.toml").unwrap();
+
+        let ubuntu = find(&distros, DistroName::Ubuntu);
+        assert_eq!(ubuntu.display_name, "Ubuntu");
+        assert_eq!(ubuntu.description, "Ubuntu from a local mirror");
+        assert_eq!(ubuntu.get_url(&Architecture::X86_64).unwrap(), "https://mirror.example.com/ubuntu-x86_64.tar.xz");
+        assert!(ubuntu.supports_arch(&Architecture::Riscv64));
+        assert!(ubuntu.get_url(&Architecture::Aarch64).unwrap().contains("ubuntu-questing-aarch64"));
+    }
+
+    #[test]
+    fn test_custom_distro() {
+        let content = r#"
+[distro.mycorp]
+display_name = "MyCorp Linux"
+package_manager = "apt"
+default_packages = ["corp-tools"]
+
+[distro.mycorp.urls]
+aarch64 = "https://rootfs.example.com/mycorp-aarch64.tar.xz"
+"#;
+        let mut distros = builtin_catalog();
+        merge_entries(&mut distros, parse_catalog(content, "catalog.toml").unwrap().entries, "catalog.toml").unwrap();
+
+        let mycorp = distros.last().unwrap();
+        assert_eq!(mycorp.name, DistroName::Custom("mycorp".to_string()));
+        assert_eq!(mycorp.package_manager, Some(PackageManager::Apt));
+        assert_eq!(mycorp.default_packages, vec!["corp-tools"]);
+        assert!(mycorp.supports_arch(&Architecture::Aarch64));
+        assert_eq!(find(&distros, DistroName::Void).package_manager, Some(PackageManager::Xbps));
+
+        let (_, parsed) = catalog_definitions(&render_entry(mycorp), "catalog.toml").unwrap();
+        assert_eq!(parsed[0].name, mycorp.name);
+        assert_eq!(parsed[0].package_manager, mycorp.package_manager);
+    }
+
+    #[test]
+    fn test_replaced_url_drops_checksum() {
+        let mut distros = catalog_definitions(&format!(
+            "[distro.void]\ndisplay_name = \"Void\"\n[distro.void.urls]\naarch64 = \"https://a/void.tar.xz\"\nx86_64 = \"https://a/void64.tar.xz\"\n[distro.void.checksums]\naarch64 = \"sha256:{0}\"\nx86_64 = \"{0}\"\n",
+            "AB".repeat(32)
+        ), "catalog.toml").unwrap().1;
+        assert_eq!(distros[0].get_checksum(&Architecture::X86_64), Some(&"ab".repeat(32)));
+
+        let entries = parse_catalog("[distro.void.urls]\naarch64 = \"https://b/void.tar.xz\"\n", "catalog.toml").unwrap().entries;
+        merge_entries(&mut distros, entries, "catalog.toml").unwrap();
+        assert_eq!(distros[0].get_checksum(&Architecture::Aarch64), None);
+        assert!(distros[0].get_checksum(&Architecture::X86_64).is_some());
+    }
+
+    #[test]
+    fn test_new_entry_requires_urls() {
+        let err = catalog_definitions("\n[distro.fedora]\ndisplay_name = \"Fedora\"\n", "catalog.toml").unwrap_err();
+        assert_eq!(err.line, 2);
+        assert!(err.message.contains("urls"));
+    }
+}
@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod tests {
+    use crate::distro::definitions::catalog::*;
+    use crate::distro::definitions::distro_definition::DistroDefinition;
+    use crate::distro::name::DistroName;
+    use crate::utils::arch::Architecture;
+    use std::fs;
+
+    fn find(distros: &[DistroDefinition], name: DistroName) -> &DistroDefinition {
+        distros.iter().find(|d| d.name == name).unwrap()
+    }
+
+    fn error_of(content: &str) -> CatalogError {
+        parse_catalog(content, "catalog.toml").unwrap_err()
+    }
+
+    #[test]
+    fn test_render_entry_roundtrip() {
+        let mut alpine = find(&builtin_catalog(), DistroName::Alpine).clone();
+        alpine.strip_components = 1;
+        alpine.installed_size_mib = Some(12);
+        alpine.checksums.insert(Architecture::Aarch64, "ab".repeat(32));
+
+        let (_, distros) = catalog_definitions(&render_entry(&alpine), "catalog.toml").unwrap();
+        let parsed = &distros[0];
+        assert_eq!(parsed.display_name, alpine.display_name);
+        assert_eq!(parsed.urls, alpine.urls);
+        assert_eq!(parsed.checksums, alpine.checksums);
+        assert_eq!(parsed.strip_components, 1);
+        assert_eq!(parsed.installed_size_mib, Some(12));
+        assert_eq!(parsed.default_packages, alpine.default_packages);
+    }
+
+    #[test]
+    fn test_append_catalog_entry() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join(CATALOG_FILE);
+        let void = find(&builtin_catalog(), DistroName::Void).clone();
+
+        assert!(append_catalog_entry(&path, &void).unwrap());
+        assert!(!append_catalog_entry(&path, &void).unwrap());
+        let catalog = parse_catalog(&fs::read_to_string(&path).unwrap(), "catalog.toml").unwrap();
+        assert_eq!(catalog.entries.len(), 1);
+    }
+
+    #[test]
+    fn test_catalog_errors_point_at_line() {
+        let err = error_of("[distro.debian]\ndisplay_name = \"Debian\"\n\n[distro.debian.urls]\narmv9 = \"https://example.com/a.tar.xz\"\n");
+        assert_eq!((err.line, err.to_string()), (5, "catalog.toml:5: 未知的架构: armv9".to_string()));
+
+        let err = error_of("[distro.alpine]\ndescription = \"x\"\nurl = \"https://example.com\"\n");
+        assert_eq!(err.line, 3);
+        assert!(err.message.contains("url"));
+
+        let err = error_of("schema_version = 1\n\n[distro.\"my corp\"]\ndisplay_name = \"MyCorp\"\n");
+        assert_eq!(err.line, 3);
+
+        let err = error_of("[distro.mycorp]\npackage_manager = \"portage\"\n");
+        assert_eq!(err.line, 2);
+
+        let err = error_of("[distro.void]\ndefault_packages = [\"vim\", 1]\n");
+        assert_eq!(err.line, 2);
+
+        let err = error_of("[distro.void]\n\n[distro.void.checksums]\naarch64 = \"1234\"\n");
+        assert_eq!(err.line, 4);
+
+        let err = error_of("schema_version = 2\n");
+        assert_eq!(err.line, 1);
+
+        let err = error_of("schema_version = 1\nserial = -1\n");
+        assert_eq!(err.line, 2);
+
+        let err = error_of("[distro.void]\ndisplay_name = \"Void\"\ndescription = \n");
+        assert_eq!(err.line, 3);
+    }
+}
@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod tests {
+    use crate::distro::definitions::catalog::*;
+    use crate::distro::definitions::distro_definition::DistroDefinition;
+    use crate::distro::name::DistroName;
+    use crate::utils::arch::Architecture;
+
+    fn find(distros: &[DistroDefinition], name: DistroName) -> &DistroDefinition {
+        distros.iter().find(|d| d.name == name).unwrap()
+    }
+
+    fn error_of(content: &str) -> CatalogError {
+        parse_catalog(content, "catalog.toml").unwrap_err()
+    }
+
+    #[test]
+    fn test_releases() {
+        let content = r#"
+[distro.debian.releases.bookworm.urls]
+aarch64 = "https://example.com/debian-bookworm-aarch64.tar.xz"
+
+[distro.debian.releases.bookworm.checksums]
+aarch64 = "ABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABAB"
+"#;
+        let mut distros = builtin_catalog();
+        merge_entries(&mut distros, parse_catalog(content, "catalog.toml").unwrap().entries, "catalog.toml").unwrap();
+
+        let debian = find(&distros, DistroName::Debian);
+        assert_eq!(debian.release.as_deref(), Some("trixie"));
+        assert!(debian.get_url(&Architecture::Aarch64).unwrap().contains("debian-trixie-aarch64"));
+        assert_eq!(debian.label(), "Debian (trixie)");
+
+        let labels: Vec<String> = debian.variants().iter().map(|d| d.label()).collect();
+        assert_eq!(labels, vec!["Debian (trixie)", "Debian (bookworm)"]);
+
+        let bookworm = debian.with_release("bookworm").unwrap();
+        assert_eq!(bookworm.get_url(&Architecture::Aarch64).unwrap(), "https://example.com/debian-bookworm-aarch64.tar.xz");
+        assert_eq!(bookworm.get_checksum(&Architecture::Aarch64), Some(&"ab".repeat(32)));
+        assert!(bookworm.get_url(&Architecture::X86_64).unwrap().contains("debian-bookworm-x86_64"));
+        assert!(debian.with_release("sid").is_none());
+
+        let entries = parse_catalog("[distro.debian]\ndefault_release = \"bookworm\"\n", "catalog.toml").unwrap().entries;
+        merge_entries(&mut distros, entries, "catalog.toml").unwrap();
+        let debian = find(&distros, DistroName::Debian);
+        assert_eq!(debian.get_url(&Architecture::Aarch64).unwrap(), "https://example.com/debian-bookworm-aarch64.tar.xz");
+
+        let (_, parsed) = catalog_definitions(&render_entry(debian), "catalog.toml").unwrap();
+        assert_eq!(parsed[0].release, debian.release);
+        assert_eq!(parsed[0].releases, debian.releases);
+    }
+
+    #[test]
+    fn test_release_errors() {
+        let err = catalog_definitions("[distro.alpine]\ndisplay_name = \"Alpine\"\ndefault_release = \"edge\"\n\n[distro.alpine.releases.stable.urls]\naarch64 = \"https://a/alpine.tar.xz\"\n", "catalog.toml").unwrap_err();
+        assert_eq!(err.line, 1);
+        assert!(err.message.contains("edge"));
+
+        let mut distros = builtin_catalog();
+        let entries = parse_catalog("[distro.debian]\n\n[distro.debian.releases.sid]\n", "catalog.toml").unwrap().entries;
+        assert_eq!(merge_entries(&mut distros, entries, "catalog.toml").unwrap_err().line, 3);
+
+        let err = error_of("[distro.debian.releases.sid]\nurl = \"https://a/sid.tar.xz\"\n");
+        assert_eq!(err.line, 2);
+    }
+}
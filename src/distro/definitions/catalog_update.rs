@@ -0,0 +1,190 @@
+use std::fs::{self, File};
+use std::path::Path;
+use std::process::Command;
+use crate::distro::definitions::catalog::{base_catalog, cached_catalog_path, catalog_definitions};
+use crate::utils::net::fetch_file;
+
+/// Namespace the index must be signed with (`ssh-keygen -Y sign -n`), so a
+/// signature made with the same key for something else is not accepted.
+pub const CATALOG_SIGNATURE_NAMESPACE: &str = "insos-catalog";
+
+/// Keys trusted without any configuration. Empty while the project
+/// publishes no signed catalog of its own; until then `catalog_key` names
+/// the key of whoever hosts `catalog_url`.
+pub const PINNED_CATALOG_KEYS: &[&str] = &[];
+
+const FETCH_TIMEOUT_SECS: u64 = 60;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CatalogUpdate {
+    Updated { serial: u64, distros: usize },
+    UpToDate { serial: u64 },
+}
+
+/// The pinned keys followed by the extra one from `catalog_key`, if any.
+pub fn trusted_catalog_keys(extra_key: Option<&str>) -> Vec<String> {
+    PINNED_CATALOG_KEYS
+        .iter()
+        .map(|key| key.to_string())
+        .chain(extra_key.map(str::trim).filter(|key| !key.is_empty()).map(String::from))
+        .collect()
+}
+
+/// Checks an `ssh-keygen -Y sign` signature of `data` against any of
+/// `public_keys`, given as OpenSSH public key lines such as `ssh-ed25519 AAAA...`.
+pub fn verify_signature(data: &Path, signature: &Path, public_keys: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let allowed_signers = signature.with_extension("allowed");
+    let signers: String = public_keys
+        .iter()
+        .map(|key| format!("{} {}\n", CATALOG_SIGNATURE_NAMESPACE, key.trim()))
+        .collect();
+    if signers.is_empty() {
+        return Err("未配置可信的目录公钥".into());
+    }
+    fs::write(&allowed_signers, signers)?;
+
+    let output = Command::new("ssh-keygen")
+        .args(["-Y", "verify", "-f"])
+        .arg(&allowed_signers)
+        .args(["-I", CATALOG_SIGNATURE_NAMESPACE, "-n", CATALOG_SIGNATURE_NAMESPACE, "-s"])
+        .arg(signature)
+        .stdin(File::open(data)?)
+        .output();
+    let _ = fs::remove_file(&allowed_signers);
+
+    if !output?.status.success() {
+        return Err("目录签名校验失败".into());
+    }
+    Ok(())
+}
+
+fn download_verified(url: &str, public_keys: &[String], index: &Path, signature: &Path) -> Result<(u64, usize), Box<dyn std::error::Error>> {
+    fetch_file(url, index, FETCH_TIMEOUT_SECS)?;
+    fetch_file(&format!("{}.sig", url), signature, FETCH_TIMEOUT_SECS)?;
+    verify_signature(index, signature, public_keys)?;
+
+    let (serial, distros) = catalog_definitions(&fs::read_to_string(index)?, url)?;
+    Ok((serial, distros.len()))
+}
+
+/// Downloads the index at `url` and its detached signature at `<url>.sig`
+/// and, once both are verified, stores the index at `cache_path` if its
+/// serial is higher than `current_serial`.
+pub fn update_catalog_from(url: &str, public_keys: &[String], cache_path: &Path, current_serial: u64) -> Result<CatalogUpdate, Box<dyn std::error::Error>> {
+    if let Some(parent) = cache_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let index = cache_path.with_extension("toml.part");
+    let signature = cache_path.with_extension("toml.part.sig");
+
+    let downloaded = download_verified(url, public_keys, &index, &signature);
+    let _ = fs::remove_file(&signature);
+    let (serial, distros) = match downloaded {
+        Ok(downloaded) if downloaded.0 > current_serial => downloaded,
+        Ok(_) => {
+            let _ = fs::remove_file(&index);
+            return Ok(CatalogUpdate::UpToDate { serial: current_serial });
+        }
+        Err(e) => {
+            let _ = fs::remove_file(&index);
+            return Err(e);
+        }
+    };
+
+    fs::rename(&index, cache_path)?;
+    Ok(CatalogUpdate::Updated { serial, distros })
+}
+
+/// `insOs catalog update`: replaces the cached catalog when the remote one
+/// is newer than both the cache and the built-in catalog.
+pub fn update_catalog(url: &str, public_keys: &[String]) -> Result<CatalogUpdate, Box<dyn std::error::Error>> {
+    let (current_serial, _) = base_catalog();
+    update_catalog_from(url, public_keys, &cached_catalog_path()?, current_serial)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_http::serve;
+    use tempfile::TempDir;
+
+    const INDEX: &str = r#"schema_version = 1
+serial = 5
+
+[distro.alpine]
+display_name = "Alpine"
+
+[distro.alpine.urls]
+aarch64 = "https://example.com/alpine-aarch64.tar.xz"
+"#;
+
+    /// Signs `content` with a fresh key, returning the signature and the public key.
+    fn sign(dir: &Path, content: &str) -> (Vec<u8>, String) {
+        let key = dir.join("key");
+        if !key.exists() {
+            let status = Command::new("ssh-keygen")
+                .args(["-q", "-t", "ed25519", "-N", "", "-C", "test", "-f"])
+                .arg(&key)
+                .status()
+                .unwrap();
+            assert!(status.success());
+        }
+
+        let data = dir.join("index.toml");
+        fs::write(&data, content).unwrap();
+        let status = Command::new("ssh-keygen")
+            .args(["-q", "-Y", "sign", "-n", CATALOG_SIGNATURE_NAMESPACE, "-f"])
+            .arg(&key)
+            .arg(&data)
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let signature = fs::read(dir.join("index.toml.sig")).unwrap();
+        fs::remove_file(dir.join("index.toml.sig")).unwrap();
+        (signature, fs::read_to_string(dir.join("key.pub")).unwrap())
+    }
+
+    fn routes(index: &str, signature: Vec<u8>) -> Vec<(String, Vec<u8>)> {
+        vec![
+            ("/catalog.toml".to_string(), index.as_bytes().to_vec()),
+            ("/catalog.toml.sig".to_string(), signature),
+        ]
+    }
+
+    #[test]
+    fn test_update_catalog_verifies_and_caches() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = temp_dir.path().join(".cache/catalog.toml");
+        let (signature, public_key) = sign(temp_dir.path(), INDEX);
+        let url = format!("{}/catalog.toml", serve(routes(INDEX, signature)));
+        let keys = trusted_catalog_keys(Some(&public_key));
+        assert_eq!(keys, vec![public_key.trim().to_string()]);
+
+        assert!(update_catalog_from(&url, &trusted_catalog_keys(None), &cache, 1).is_err());
+        let update = update_catalog_from(&url, &keys, &cache, 1).unwrap();
+        assert_eq!(update, CatalogUpdate::Updated { serial: 5, distros: 1 });
+        assert_eq!(fs::read_to_string(&cache).unwrap(), INDEX);
+        assert!(!cache.with_extension("toml.part").exists());
+
+        let update = update_catalog_from(&url, &keys, &cache, 5).unwrap();
+        assert_eq!(update, CatalogUpdate::UpToDate { serial: 5 });
+    }
+
+    #[test]
+    fn test_update_catalog_rejects_bad_signature() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = temp_dir.path().join(".cache/catalog.toml");
+        let (signature, public_key) = sign(temp_dir.path(), INDEX);
+        let public_key = trusted_catalog_keys(Some(&public_key));
+        let tampered = INDEX.replace("example.com", "attacker.example");
+        let url = format!("{}/catalog.toml", serve(routes(&tampered, signature)));
+
+        assert!(update_catalog_from(&url, &public_key, &cache, 1).is_err());
+        assert!(!cache.exists());
+        assert!(!cache.with_extension("toml.part").exists());
+
+        let missing = format!("{}/missing.toml", url.trim_end_matches("/catalog.toml"));
+        assert!(update_catalog_from(&missing, &public_key, &cache, 1).is_err());
+    }
+}
@@ -1,6 +1,14 @@
 use std::collections::HashMap;
 use crate::utils::arch::Architecture;
 use crate::distro::name::DistroName;
+use crate::distro::package_manager::PackageManager;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistroRelease {
+    pub name: String,
+    pub urls: HashMap<Architecture, String>,
+    pub checksums: HashMap<Architecture, String>,
+}
 
 #[derive(Debug, Clone)]
 pub struct DistroDefinition {
@@ -9,6 +17,19 @@ pub struct DistroDefinition {
     pub urls: HashMap<Architecture, String>,
     pub description: String,
     pub default_packages: Vec<String>,
+    /// Lowercase hex SHA-256 of each architecture's archive, when known.
+    pub checksums: HashMap<Architecture, String>,
+    /// Leading path components to drop when extracting (`tar --strip-components`).
+    pub strip_components: u32,
+    /// Package manager of the rootfs, for when it cannot be detected.
+    pub package_manager: Option<PackageManager>,
+    pub releases: Vec<DistroRelease>,
+    /// The release `urls` and `checksums` belong to: the default one unless
+    /// [`DistroDefinition::with_release`] picked another. `None` when the
+    /// default URLs are not a named release.
+    pub release: Option<String>,
+    /// Approximate size of the extracted rootfs, for the free-space check.
+    pub installed_size_mib: Option<u64>,
 }
 
 impl DistroDefinition {
@@ -19,4 +40,42 @@ impl DistroDefinition {
     pub fn supports_arch(&self, arch: &Architecture) -> bool {
         self.urls.contains_key(arch)
     }
-}
\ No newline at end of file
+    
+    pub fn get_checksum(&self, arch: &Architecture) -> Option<&String> {
+        self.checksums.get(arch)
+    }
+    
+    pub fn get_release(&self, name: &str) -> Option<&DistroRelease> {
+        self.releases.iter().find(|release| release.name == name)
+    }
+    
+    /// The same distro with the URLs of release `name`.
+    pub fn with_release(&self, name: &str) -> Option<DistroDefinition> {
+        let release = self.get_release(name)?;
+        let mut distro = self.clone();
+        distro.urls = release.urls.clone();
+        distro.checksums = release.checksums.clone();
+        distro.release = Some(release.name.clone());
+        Some(distro)
+    }
+    
+    /// The default selection followed by every other release.
+    pub fn variants(&self) -> Vec<DistroDefinition> {
+        let mut variants = vec![self.clone()];
+        variants.extend(
+            self.releases
+                .iter()
+                .filter(|release| Some(&release.name) != self.release.as_ref())
+                .filter_map(|release| self.with_release(&release.name)),
+        );
+        variants
+    }
+    
+    /// Display name with the release, e.g. `Debian (trixie)`.
+    pub fn label(&self) -> String {
+        match &self.release {
+            Some(release) => format!("{} ({})", self.display_name, release),
+            None => self.display_name.clone(),
+        }
+    }
+}
@@ -1,9 +1,12 @@
 pub mod base;
-pub mod adelie;
-pub mod debian_core;
-pub mod debian;
-pub mod arch_core;
-pub mod arch;
-pub mod redhat;
-pub mod others;
+pub mod catalog;
+pub mod catalog_entry;
+pub mod catalog_fields;
+pub mod catalog_merge;
+pub mod catalog_parse;
+pub mod catalog_render;
+pub mod catalog_source;
+pub mod catalog_update;
 pub mod distro_definition;
+pub mod plugin;
+pub mod search;
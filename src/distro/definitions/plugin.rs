@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+use crate::distro::definitions::catalog::{normalize_sha256, CatalogError};
+use crate::distro::definitions::distro_definition::DistroDefinition;
+use crate::distro::name::DistroName;
+use crate::utils::arch::Architecture;
+
+/// Splits `NAME=value` or `NAME[index]=value` into its parts. Only top-level
+/// assignments count; indented lines belong to functions such as
+/// `distro_setup` and are skipped.
+fn split_assignment(line: &str) -> Option<(&str, Option<&str>, &str)> {
+    let (target, value) = line.split_once('=')?;
+    let (name, index) = match target.split_once('[') {
+        Some((name, index)) => (name, Some(index.strip_suffix(']')?)),
+        None => (target, None),
+    };
+    let valid_name = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    valid_name.then_some((name, index, value))
+}
+
+/// Value of a shell word: single-quoted, double-quoted or bare, followed by
+/// nothing but an optional comment. Expansions are rejected since the
+/// plugin is never executed.
+fn shell_word(raw: &str) -> Result<String, String> {
+    let (value, rest) = if let Some(quoted) = raw.strip_prefix('\'') {
+        let end = quoted.find('\'').ok_or("未闭合的引号")?;
+        (quoted[..end].to_string(), &quoted[end + 1..])
+    } else if let Some(quoted) = raw.strip_prefix('"') {
+        let mut value = String::new();
+        let mut chars = quoted.char_indices();
+        let end = loop {
+            match chars.next().ok_or("未闭合的引号")? {
+                (i, '"') => break i,
+                (_, '\\') => value.push(chars.next().ok_or("未闭合的引号")?.1),
+                (_, '$') | (_, '`') => return Err("不支持变量展开".to_string()),
+                (_, c) => value.push(c),
+            }
+        };
+        (value, &quoted[end + 1..])
+    } else {
+        let end = raw.find(char::is_whitespace).unwrap_or(raw.len());
+        if raw[..end].contains(['$', '`', '"', '\'']) {
+            return Err("不支持变量展开".to_string());
+        }
+        (raw[..end].to_string(), &raw[end..])
+    };
+
+    let rest = rest.trim_start();
+    if !rest.is_empty() && !rest.starts_with('#') {
+        return Err(format!("无法解析的内容: {}", rest));
+    }
+    Ok(value)
+}
+
+/// Builds a definition from a proot-distro plugin (`DISTRO_NAME`,
+/// `DISTRO_COMMENT`, `TARBALL_URL[arch]`, `TARBALL_SHA256[arch]`,
+/// `TARBALL_STRIP_OPT`) by reading its assignments, never running it.
+pub fn parse_plugin(content: &str, file: &str, name: DistroName) -> Result<DistroDefinition, CatalogError> {
+    let error = |line: usize, message: String| CatalogError { file: file.to_string(), line, message };
+
+    let mut display_name = None;
+    let mut description = String::new();
+    let mut urls = HashMap::new();
+    let mut checksums = HashMap::new();
+    let mut strip_components = 0;
+
+    for (index, line) in content.lines().enumerate() {
+        let line_number = index + 1;
+        if line.starts_with(char::is_whitespace) || line.starts_with('#') {
+            continue;
+        }
+        let Some((variable, key, raw)) = split_assignment(line) else {
+            continue;
+        };
+        if !matches!(variable, "DISTRO_NAME" | "DISTRO_COMMENT" | "TARBALL_URL" | "TARBALL_SHA256" | "TARBALL_STRIP_OPT") {
+            continue;
+        }
+
+        let value = shell_word(raw).map_err(|message| error(line_number, message))?;
+        let arch = match key {
+            Some(key) => {
+                let key = key.trim_matches(['\'', '"']);
+                Some(Architecture::from_str(key).ok_or_else(|| error(line_number, format!("未知的架构: {}", key)))?)
+            }
+            None => None,
+        };
+
+        match (variable, arch) {
+            ("DISTRO_NAME", None) => display_name = Some(value),
+            ("DISTRO_COMMENT", None) => description = value,
+            ("TARBALL_STRIP_OPT", None) => {
+                strip_components = value
+                    .parse()
+                    .map_err(|_| error(line_number, format!("TARBALL_STRIP_OPT 应为非负整数: {}", value)))?;
+            }
+            ("TARBALL_URL", Some(arch)) => {
+                urls.insert(arch, value);
+            }
+            ("TARBALL_SHA256", Some(arch)) => {
+                let checksum = normalize_sha256(&value)
+                    .ok_or_else(|| error(line_number, format!("无效的 SHA-256: {}", value)))?;
+                checksums.insert(arch, checksum);
+            }
+            _ => return Err(error(line_number, format!("{} 的写法无效", variable))),
+        }
+    }
+
+    let display_name = display_name.ok_or_else(|| error(1, "缺少 DISTRO_NAME".to_string()))?;
+    if urls.is_empty() {
+        return Err(error(1, "缺少 TARBALL_URL".to_string()));
+    }
+    if let Some(arch) = checksums.keys().find(|arch| !urls.contains_key(arch)) {
+        return Err(error(1, format!("{} 有 TARBALL_SHA256 但没有 TARBALL_URL", arch.to_str())));
+    }
+
+    Ok(DistroDefinition {
+        name,
+        display_name,
+        urls,
+        description,
+        default_packages: Vec::new(),
+        checksums,
+        strip_components,
+        package_manager: None,
+        releases: Vec::new(),
+        release: None,
+        installed_size_mib: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLUGIN: &str = r#"# This is a default distribution plug-in.
+DISTRO_NAME="Alpine Linux"
+DISTRO_COMMENT="Rolling release branch (edge)."
+
+TARBALL_STRIP_OPT=1
+TARBALL_URL['aarch64']="https://github.com/termux/proot-distro/releases/download/v4.30.1/alpine-aarch64-pd-v4.30.1.tar.xz"
+TARBALL_SHA256['aarch64']="1f3a6d1c5e9c0b7d3a2e4f6a8b0c2d4e6f8a0b2c4d6e8f0a2b4c6d8e0f2a4b6c"
+TARBALL_URL['x86_64']='https://github.com/termux/proot-distro/releases/download/v4.30.1/alpine-x86_64-pd-v4.30.1.tar.xz' # mirror
+TARBALL_SHA256[x86_64]=AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA
+
+distro_setup() {
+	TARBALL_URL['arm']="$(echo ignored)"
+	run_proot_cmd apk upgrade
+}
+"#;
+
+    #[test]
+    fn test_parse_plugin() {
+        let distro = parse_plugin(PLUGIN, "alpine.sh", DistroName::Alpine).unwrap();
+        assert_eq!(distro.display_name, "Alpine Linux");
+        assert_eq!(distro.description, "Rolling release branch (edge).");
+        assert_eq!(distro.strip_components, 1);
+        assert_eq!(distro.urls.len(), 2);
+        assert!(distro.get_url(&Architecture::X86_64).unwrap().ends_with("alpine-x86_64-pd-v4.30.1.tar.xz"));
+        assert!(!distro.supports_arch(&Architecture::Arm));
+        assert_eq!(distro.get_checksum(&Architecture::Aarch64).unwrap(), "1f3a6d1c5e9c0b7d3a2e4f6a8b0c2d4e6f8a0b2c4d6e8f0a2b4c6d8e0f2a4b6c");
+        assert_eq!(distro.get_checksum(&Architecture::X86_64).unwrap(), &"a".repeat(64));
+    }
+
+    #[test]
+    fn test_parse_plugin_errors() {
+        let expansion = "DISTRO_NAME=\"Alpine\"\nTARBALL_URL['aarch64']=\"${BASE}/alpine.tar.xz\"\n";
+        let err = parse_plugin(expansion, "alpine.sh", DistroName::Alpine).unwrap_err();
+        assert_eq!(err.line, 2);
+
+        let unknown_arch = "DISTRO_NAME=\"Alpine\"\nTARBALL_URL['mips']=\"https://a/b.tar.xz\"\n";
+        assert_eq!(parse_plugin(unknown_arch, "alpine.sh", DistroName::Alpine).unwrap_err().line, 2);
+
+        let no_urls = "DISTRO_NAME=\"Alpine\"\n";
+        assert!(parse_plugin(no_urls, "alpine.sh", DistroName::Alpine).unwrap_err().message.contains("TARBALL_URL"));
+    }
+}
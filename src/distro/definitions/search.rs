@@ -0,0 +1,80 @@
+use crate::distro::definitions::base::get_all_distros;
+use crate::distro::definitions::distro_definition::DistroDefinition;
+use crate::distro::package_manager::PackageManager;
+use crate::utils::arch::Architecture;
+
+/// Criteria shared by `search`, `available` and the interactive menu. Unset
+/// fields match everything.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DistroFilter {
+    /// Case-insensitive text looked up in the id, display name, release and
+    /// description.
+    pub term: Option<String>,
+    pub arch: Option<Architecture>,
+    pub family: Option<PackageManager>,
+}
+
+impl DistroFilter {
+    pub fn matches(&self, distro: &DistroDefinition) -> bool {
+        let term_matches = self.term.as_ref().is_none_or(|term| {
+            let term = term.to_lowercase();
+            [distro.name.as_str(), &distro.display_name, distro.release.as_deref().unwrap_or_default(), &distro.description]
+                .iter()
+                .any(|field| field.to_lowercase().contains(&term))
+        });
+        term_matches
+            && self.arch.is_none_or(|arch| distro.supports_arch(&arch))
+            && self.family.is_none_or(|family| distro.package_manager == Some(family))
+    }
+}
+
+/// Every release of every distro in the catalog that passes `filter`,
+/// default release first.
+pub fn search_distros(filter: &DistroFilter) -> Vec<DistroDefinition> {
+    filter_variants(&get_all_distros(), filter)
+}
+
+pub fn filter_variants(distros: &[DistroDefinition], filter: &DistroFilter) -> Vec<DistroDefinition> {
+    distros
+        .iter()
+        .flat_map(DistroDefinition::variants)
+        .filter(|distro| filter.matches(distro))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distro::definitions::catalog::builtin_catalog;
+
+    fn ids(distros: &[DistroDefinition]) -> Vec<String> {
+        distros.iter().map(|d| d.name.as_str().to_string()).collect()
+    }
+
+    #[test]
+    fn test_filter_by_term() {
+        let catalog = builtin_catalog();
+        let filter = DistroFilter { term: Some("ARCH LINUX".to_string()), ..Default::default() };
+        assert_eq!(ids(&filter_variants(&catalog, &filter)), vec!["archlinux", "manjaro", "artix"]);
+
+        let by_release = DistroFilter { term: Some("trixie".to_string()), ..Default::default() };
+        let found = filter_variants(&catalog, &by_release);
+        assert_eq!(ids(&found), vec!["debian"]);
+        assert_eq!(found[0].release.as_deref(), Some("trixie"));
+    }
+
+    #[test]
+    fn test_filter_by_family_and_arch() {
+        let catalog = builtin_catalog();
+        let apt = DistroFilter { family: Some(PackageManager::Apt), ..Default::default() };
+        let found = filter_variants(&catalog, &apt);
+        assert!(!found.is_empty());
+        assert!(found.iter().all(|d| d.package_manager == Some(PackageManager::Apt)));
+        assert!(ids(&found).contains(&"ubuntu".to_string()));
+
+        let arm = DistroFilter { arch: Some(Architecture::Aarch64), family: Some(PackageManager::Pacman), ..Default::default() };
+        assert!(filter_variants(&catalog, &arm).iter().all(|d| d.supports_arch(&Architecture::Aarch64) && d.package_manager == Some(PackageManager::Pacman)));
+
+        assert_eq!(filter_variants(&catalog, &DistroFilter::default()).len(), catalog.iter().map(|d| d.variants().len()).sum::<usize>());
+    }
+}
@@ -1,49 +1,99 @@
-use crate::utils::permissions;
-use std::time::{SystemTime, UNIX_EPOCH};
+use crate::system::backend::BackendKind;
+use crate::utils::arch::Architecture;
 
-#[derive(Debug, Clone)]
+pub const META_SCHEMA_VERSION: i64 = 1;
+pub const META_FILE: &str = "meta.toml";
+pub const LEGACY_META_FILE: &str = "meta.txt";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InstallMode {
+    #[default]
+    Standard,
+    Minimal,
+}
+
+impl InstallMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InstallMode::Standard => "standard",
+            InstallMode::Minimal => "minimal",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "standard" => Some(InstallMode::Standard),
+            "minimal" => Some(InstallMode::Minimal),
+            _ => None,
+        }
+    }
+}
+
+/// Result of the last disk usage scan of the system directory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiskUsage {
+    pub apparent_bytes: u64,
+    pub disk_bytes: u64,
+    pub scanned_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct SystemMeta {
     pub name: String,
-    pub os_type: String,
-    pub created_at: String,
-    pub user_group: String,
-    pub permissions: String,
+    /// Distro identifier as used by `install`, e.g. `debian`.
+    pub distro: String,
+    pub release: Option<String>,
+    pub arch: Architecture,
+    /// `None` for systems migrated from a `meta.txt` that did not record it.
+    pub created_at: Option<String>,
+    pub install_mode: InstallMode,
+    pub default_user: String,
+    pub backend: BackendKind,
+    /// True when `arch` runs through qemu-user on this host.
+    pub emulated: bool,
+    pub source_url: Option<String>,
+    /// `sha256:<hex>` of the downloaded rootfs archive.
+    pub checksum: Option<String>,
     pub mirror_url: Option<String>,
+    pub download_size: Option<u64>,
+    pub disk_usage: Option<DiskUsage>,
+    pub last_login: Option<String>,
+    /// Desktop environment installed by `insOs desktop`.
+    pub desktop: Option<String>,
+    /// Port of the guest's sshd, set by `insOs ssh-setup`.
+    pub ssh_port: Option<u16>,
+}
+
+pub fn current_timestamp() -> String {
+    chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string()
 }
 
 impl SystemMeta {
-    pub fn new(name: String, os_type: String) -> Self {
-        let created_at = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map(|d| {
-                let secs = d.as_secs();
-                format!(
-                    "{}",
-                    chrono::DateTime::from_timestamp(secs as i64, 0)
-                        .map(|dt| dt.format("%Y-%m-%dT%H:%M:%SZ").to_string())
-                        .unwrap_or_else(|| format!("2025-01-01T00:00:00Z"))
-                )
-            })
-            .unwrap_or_else(|_| "2025-01-01T00:00:00Z".to_string());
-        
-        let user_group = permissions::get_current_user()
-            .unwrap_or_else(|_| "unknown".to_string());
-        
-        let permissions = if permissions::is_root_user() {
-            "755".to_string()
-        } else {
-            "644".to_string()
-        };
-        
+    pub fn new(name: String, distro: String, arch: Architecture) -> Self {
         Self {
             name,
-            os_type,
-            created_at,
-            user_group,
-            permissions,
+            distro,
+            release: None,
+            arch,
+            created_at: Some(current_timestamp()),
+            install_mode: InstallMode::default(),
+            default_user: "root".to_string(),
+            backend: BackendKind::default(),
+            emulated: false,
+            source_url: None,
+            checksum: None,
             mirror_url: None,
+            download_size: None,
+            disk_usage: None,
+            last_login: None,
+            desktop: None,
+            ssh_port: None,
         }
     }
+
+    pub fn emulated_arch(&self) -> Option<Architecture> {
+        self.emulated.then_some(self.arch)
+    }
 }
 
 #[cfg(test)]
@@ -52,12 +102,15 @@ mod tests {
 
     #[test]
     fn test_system_meta_new() {
-        let meta = SystemMeta::new("test-system".to_string(), "ubuntu".to_string());
+        let meta = SystemMeta::new("test-system".to_string(), "ubuntu".to_string(), Architecture::Aarch64);
         assert_eq!(meta.name, "test-system");
-        assert_eq!(meta.os_type, "ubuntu");
-        assert!(!meta.created_at.is_empty());
-        assert!(!meta.user_group.is_empty());
-        assert!(!meta.permissions.is_empty());
-        assert!(meta.mirror_url.is_none());
+        assert_eq!(meta.distro, "ubuntu");
+        assert_eq!(meta.arch, Architecture::Aarch64);
+        assert!(meta.created_at.as_ref().unwrap().ends_with('Z'));
+        assert_eq!(meta.default_user, "root");
+        assert_eq!(meta.install_mode, InstallMode::Standard);
+        assert_eq!(meta.backend, BackendKind::Proot);
+        assert!(meta.emulated_arch().is_none());
+        assert!(meta.disk_usage.is_none());
     }
-}
\ No newline at end of file
+}
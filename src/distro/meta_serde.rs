@@ -1,126 +1,292 @@
 use std::collections::HashMap;
-use super::SystemMeta;
-use crate::utils::permissions;
-use std::time::{SystemTime, UNIX_EPOCH};
+use super::meta::{DiskUsage, InstallMode, SystemMeta, META_SCHEMA_VERSION};
+use crate::system::backend::BackendKind;
+use crate::utils::arch::Architecture;
+
+type MetaResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+fn quote(value: &str) -> String {
+    toml::Value::String(value.to_string()).to_string()
+}
+
+fn type_error(key: &str, expected: &str) -> Box<dyn std::error::Error> {
+    format!("字段 {} 的类型应为{}", key, expected).into()
+}
+
+fn optional_str(table: &toml::Table, key: &str) -> MetaResult<Option<String>> {
+    match table.get(key) {
+        Some(toml::Value::String(value)) => Ok(Some(value.clone())),
+        Some(_) => Err(type_error(key, "字符串")),
+        None => Ok(None),
+    }
+}
+
+fn required_str(table: &toml::Table, key: &str) -> MetaResult<String> {
+    optional_str(table, key)?.ok_or_else(|| format!("缺少字段 {}", key).into())
+}
+
+fn optional_u64(table: &toml::Table, key: &str) -> MetaResult<Option<u64>> {
+    match table.get(key) {
+        Some(toml::Value::Integer(value)) => u64::try_from(*value)
+            .map(Some)
+            .map_err(|_| type_error(key, "非负整数")),
+        Some(_) => Err(type_error(key, "整数")),
+        None => Ok(None),
+    }
+}
+
+fn required_u64(table: &toml::Table, key: &str) -> MetaResult<u64> {
+    optional_u64(table, key)?.ok_or_else(|| format!("缺少字段 {}", key).into())
+}
+
+fn optional_table<'a>(table: &'a toml::Table, key: &str) -> MetaResult<Option<&'a toml::Table>> {
+    match table.get(key) {
+        Some(toml::Value::Table(value)) => Ok(Some(value)),
+        Some(_) => Err(type_error(key, "表")),
+        None => Ok(None),
+    }
+}
+
+fn required_enum<T>(table: &toml::Table, key: &str, parse: impl Fn(&str) -> Option<T>) -> MetaResult<T> {
+    let value = required_str(table, key)?;
+    parse(&value).ok_or_else(|| format!("字段 {} 的值无效: {}", key, value).into())
+}
 
 impl SystemMeta {
-    pub fn to_string(&self) -> String {
-        let mut result = format!("name = {}\n", self.name);
-        result.push_str(&format!("os_type = {}\n", self.os_type));
-        result.push_str(&format!("created_at = {}\n", self.created_at));
-        result.push_str(&format!("user_group = {}\n", self.user_group));
-        result.push_str(&format!("permissions = {}\n", self.permissions));
+    pub fn to_toml(&self) -> String {
+        let mut result = format!("schema_version = {}\n", META_SCHEMA_VERSION);
+        result.push_str(&format!("name = {}\n", quote(&self.name)));
+        result.push_str(&format!("distro = {}\n", quote(&self.distro)));
+        if let Some(release) = &self.release {
+            result.push_str(&format!("release = {}\n", quote(release)));
+        }
+        result.push_str(&format!("arch = {}\n", quote(self.arch.to_str())));
+        if let Some(created_at) = &self.created_at {
+            result.push_str(&format!("created_at = {}\n", quote(created_at)));
+        }
+        result.push_str(&format!("install_mode = {}\n", quote(self.install_mode.as_str())));
+        result.push_str(&format!("default_user = {}\n", quote(&self.default_user)));
+        result.push_str(&format!("backend = {}\n", quote(self.backend.as_str())));
+        result.push_str(&format!("emulated = {}\n", self.emulated));
+        if let Some(last_login) = &self.last_login {
+            result.push_str(&format!("last_login = {}\n", quote(last_login)));
+        }
+        if let Some(desktop) = &self.desktop {
+            result.push_str(&format!("desktop = {}\n", quote(desktop)));
+        }
+        if let Some(port) = self.ssh_port {
+            result.push_str(&format!("ssh_port = {}\n", port));
+        }
+
+        result.push_str("\n[source]\n");
+        if let Some(url) = &self.source_url {
+            result.push_str(&format!("url = {}\n", quote(url)));
+        }
+        if let Some(checksum) = &self.checksum {
+            result.push_str(&format!("checksum = {}\n", quote(checksum)));
+        }
         if let Some(mirror) = &self.mirror_url {
-            result.push_str(&format!("mirror_url = {}\n", mirror));
+            result.push_str(&format!("mirror = {}\n", quote(mirror)));
+        }
+        if let Some(size) = self.download_size {
+            result.push_str(&format!("download_size = {}\n", size));
+        }
+
+        if let Some(usage) = &self.disk_usage {
+            result.push_str("\n[disk_usage]\n");
+            result.push_str(&format!("apparent_bytes = {}\n", usage.apparent_bytes));
+            result.push_str(&format!("disk_bytes = {}\n", usage.disk_bytes));
+            result.push_str(&format!("scanned_at = {}\n", quote(&usage.scanned_at)));
         }
+
         result
     }
-    
-    pub fn from_string(content: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        let mut map = HashMap::new();
-        
-        for line in content.lines() {
-            if let Some((key, value)) = line.split_once('=') {
-                map.insert(key.trim().to_string(), value.trim().to_string());
-            }
+
+    /// Parses `meta.toml`, rejecting missing required fields, wrong types and
+    /// metadata written by a newer schema.
+    pub fn from_toml(content: &str) -> MetaResult<Self> {
+        let table: toml::Table = content.parse()?;
+
+        let version = match table.get("schema_version") {
+            Some(toml::Value::Integer(version)) => *version,
+            Some(_) => return Err(type_error("schema_version", "整数")),
+            None => return Err("缺少字段 schema_version".into()),
+        };
+        if version > META_SCHEMA_VERSION || version < 1 {
+            return Err(format!("不支持的元数据版本: {} (当前版本 {})", version, META_SCHEMA_VERSION).into());
         }
-        
-        let user_group = map.get("user_group")
-            .map(|s| s.to_string())
-            .or_else(|| permissions::get_current_user().ok())
-            .unwrap_or_else(|| "unknown".to_string());
-        
-        let permissions = map.get("permissions")
-            .map(|s| s.to_string())
-            .unwrap_or_else(|| {
-                if permissions::is_root_user() {
-                    "755".to_string()
-                } else {
-                    "644".to_string()
-                }
-            });
-        
-        let created_at = map.get("created_at")
-            .map(|s| s.to_string())
-            .or_else(|| {
-                SystemTime::now()
-                    .duration_since(UNIX_EPOCH)
-                    .ok()
-                    .map(|d| {
-                        let secs = d.as_secs();
-                        chrono::DateTime::from_timestamp(secs as i64, 0)
-                            .map(|dt| dt.format("%Y-%m-%dT%H:%M:%SZ").to_string())
-                            .unwrap_or_else(|| "2025-01-01T00:00:00Z".to_string())
-                    })
-            })
-            .unwrap_or_else(|| "2025-01-01T00:00:00Z".to_string());
-        
+
+        let emulated = match table.get("emulated") {
+            Some(toml::Value::Boolean(value)) => *value,
+            Some(_) => return Err(type_error("emulated", "布尔值")),
+            None => false,
+        };
+
+        let ssh_port = match optional_u64(&table, "ssh_port")? {
+            Some(port) => Some(u16::try_from(port).map_err(|_| type_error("ssh_port", "端口号"))?),
+            None => None,
+        };
+
+        let empty = toml::Table::new();
+        let source = optional_table(&table, "source")?.unwrap_or(&empty);
+        let disk_usage = match optional_table(&table, "disk_usage")? {
+            Some(usage) => Some(DiskUsage {
+                apparent_bytes: required_u64(usage, "apparent_bytes")?,
+                disk_bytes: required_u64(usage, "disk_bytes")?,
+                scanned_at: required_str(usage, "scanned_at")?,
+            }),
+            None => None,
+        };
+
         Ok(SystemMeta {
-            name: map.get("name").unwrap_or(&"".to_string()).clone(),
-            os_type: map.get("os_type").unwrap_or(&"".to_string()).clone(),
-            created_at,
-            user_group,
-            permissions,
-            mirror_url: map.get("mirror_url").cloned(),
+            name: required_str(&table, "name")?,
+            distro: required_str(&table, "distro")?,
+            release: optional_str(&table, "release")?,
+            arch: required_enum(&table, "arch", Architecture::from_str)?,
+            created_at: optional_str(&table, "created_at")?,
+            install_mode: required_enum(&table, "install_mode", InstallMode::from_str)?,
+            default_user: optional_str(&table, "default_user")?.unwrap_or_else(|| "root".to_string()),
+            backend: required_enum(&table, "backend", BackendKind::from_str)?,
+            emulated,
+            source_url: optional_str(source, "url")?,
+            checksum: optional_str(source, "checksum")?,
+            mirror_url: optional_str(source, "mirror")?,
+            download_size: optional_u64(source, "download_size")?,
+            disk_usage,
+            last_login: optional_str(&table, "last_login")?,
+            desktop: optional_str(&table, "desktop")?,
+            ssh_port,
         })
     }
+
+    /// Converts an old `meta.txt`. It stored the distro's display name and no
+    /// architecture for native systems, so `host_arch` fills that in.
+    pub fn from_legacy(content: &str, system_id: &str, host_arch: Architecture) -> MetaResult<Self> {
+        let map: HashMap<&str, &str> = content
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, value)| (key.trim(), value.trim()))
+            .collect();
+
+        let os_type = map.get("os_type").copied().unwrap_or_default();
+        let distro = crate::distro::get_all_distros()
+            .into_iter()
+            .find(|d| d.name.to_string().eq_ignore_ascii_case(os_type) || d.name.as_str().eq_ignore_ascii_case(os_type))
+            .map(|d| d.name.as_str().to_string())
+            .unwrap_or_else(|| os_type.to_lowercase().replace(' ', ""));
+
+        let emulated_arch = match map.get("emulated_arch") {
+            Some(value) => Some(Architecture::from_str(value).ok_or_else(|| format!("未知的架构: {}", value))?),
+            None => None,
+        };
+
+        let name = map.get("name").copied().filter(|name| !name.is_empty()).unwrap_or(system_id);
+        let mut meta = SystemMeta::new(name.to_string(), distro, emulated_arch.unwrap_or(host_arch));
+        meta.emulated = emulated_arch.is_some();
+        meta.created_at = map.get("created_at").map(|s| s.to_string());
+        meta.mirror_url = map.get("mirror_url").map(|s| s.to_string());
+        meta.desktop = map.get("desktop").map(|s| s.to_string());
+        if let Some(value) = map.get("backend") {
+            meta.backend = BackendKind::from_str(value).ok_or_else(|| format!("未知的容器后端: {}", value))?;
+        }
+        if let Some(value) = map.get("ssh_port") {
+            meta.ssh_port = Some(value.parse().map_err(|_| format!("无效的 SSH 端口: {}", value))?);
+        }
+
+        Ok(meta)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn sample_meta() -> SystemMeta {
+        let mut meta = SystemMeta::new("debian1".to_string(), "debian".to_string(), Architecture::X86_64);
+        meta.created_at = Some("2025-01-01T00:00:00Z".to_string());
+        meta.release = Some("bookworm".to_string());
+        meta.backend = BackendKind::Chroot;
+        meta.emulated = true;
+        meta.install_mode = InstallMode::Minimal;
+        meta.source_url = Some("https://example.com/debian.tar.xz".to_string());
+        meta.checksum = Some("sha256:abcd".to_string());
+        meta.download_size = Some(1024);
+        meta.disk_usage = Some(DiskUsage {
+            apparent_bytes: 2048,
+            disk_bytes: 4096,
+            scanned_at: "2025-01-02T00:00:00Z".to_string(),
+        });
+        meta.desktop = Some("xfce4".to_string());
+        meta.ssh_port = Some(2222);
+        meta
+    }
+
     #[test]
-    fn test_system_meta_to_string() {
-        let meta = SystemMeta {
-            name: "test".to_string(),
-            os_type: "debian".to_string(),
-            created_at: "2025-01-01T00:00:00Z".to_string(),
-            user_group: "user:group".to_string(),
-            permissions: "755".to_string(),
-            mirror_url: Some("https://mirror.example.com".to_string()),
-        };
-        
-        let content = meta.to_string();
-        assert!(content.contains("name = test"));
-        assert!(content.contains("os_type = debian"));
-        assert!(content.contains("created_at = 2025-01-01T00:00:00Z"));
-        assert!(content.contains("user_group = user:group"));
-        assert!(content.contains("permissions = 755"));
-        assert!(content.contains("mirror_url = https://mirror.example.com"));
+    fn test_system_meta_toml_roundtrip() {
+        let meta = sample_meta();
+        let content = meta.to_toml();
+
+        assert!(content.starts_with("schema_version = 1\n"));
+        assert!(content.contains("arch = \"x86_64\""));
+        assert!(content.contains("[disk_usage]"));
+        assert_eq!(SystemMeta::from_toml(&content).unwrap(), meta);
+
+        let minimal = SystemMeta::new("alpine1".to_string(), "alpine".to_string(), Architecture::Aarch64);
+        assert_eq!(SystemMeta::from_toml(&minimal.to_toml()).unwrap(), minimal);
+    }
+
+    #[test]
+    fn test_system_meta_from_toml_strict() {
+        let content = sample_meta().to_toml();
+
+        let missing = content.replace("arch = \"x86_64\"\n", "");
+        assert!(SystemMeta::from_toml(&missing).unwrap_err().to_string().contains("arch"));
+
+        let wrong_type = content.replace("ssh_port = 2222", "ssh_port = \"2222\"");
+        assert!(SystemMeta::from_toml(&wrong_type).unwrap_err().to_string().contains("ssh_port"));
+
+        let bad_backend = content.replace("backend = \"chroot\"", "backend = \"lxc\"");
+        assert!(SystemMeta::from_toml(&bad_backend).unwrap_err().to_string().contains("backend"));
+
+        let future = content.replace("schema_version = 1", "schema_version = 2");
+        assert!(SystemMeta::from_toml(&future).is_err());
     }
 
     #[test]
-    fn test_system_meta_from_string() {
-        let content = r#"name = test-system
-os_type = fedora
+    fn test_system_meta_from_legacy() {
+        let content = r#"name = arch-x86
+os_type = Arch Linux
 created_at = 2025-01-01T12:00:00Z
-user_group = testuser:testgroup
+user_group = u0_a123:u0_a123
 permissions = 644
 mirror_url = https://mirror.example.com
+backend = chroot
+emulated_arch = x86_64
+ssh_port = 2200
 "#;
-        
-        let meta = SystemMeta::from_string(content).unwrap();
-        assert_eq!(meta.name, "test-system");
-        assert_eq!(meta.os_type, "fedora");
-        assert_eq!(meta.created_at, "2025-01-01T12:00:00Z");
-        assert_eq!(meta.user_group, "testuser:testgroup");
-        assert_eq!(meta.permissions, "644");
+
+        let meta = SystemMeta::from_legacy(content, "arch-x86", Architecture::Aarch64).unwrap();
+        assert_eq!(meta.name, "arch-x86");
+        assert_eq!(meta.distro, "archlinux");
+        assert_eq!(meta.arch, Architecture::X86_64);
+        assert_eq!(meta.emulated_arch(), Some(Architecture::X86_64));
+        assert_eq!(meta.created_at.as_deref(), Some("2025-01-01T12:00:00Z"));
+        assert_eq!(meta.backend, BackendKind::Chroot);
         assert_eq!(meta.mirror_url, Some("https://mirror.example.com".to_string()));
+        assert_eq!(meta.ssh_port, Some(2200));
     }
 
     #[test]
-    fn test_system_meta_from_string_partial() {
-        let content = r#"name = partial-system
-os_type = alpine
-"#;
-        
-        let meta = SystemMeta::from_string(content).unwrap();
-        assert_eq!(meta.name, "partial-system");
-        assert_eq!(meta.os_type, "alpine");
-        assert!(!meta.created_at.is_empty());
-        assert!(!meta.user_group.is_empty());
-        assert!(!meta.permissions.is_empty());
-        assert!(meta.mirror_url.is_none());
+    fn test_system_meta_from_legacy_partial() {
+        let meta = SystemMeta::from_legacy("os_type = Alpine\n", "alpine1", Architecture::Aarch64).unwrap();
+        assert_eq!(meta.name, "alpine1");
+        assert_eq!(meta.distro, "alpine");
+        assert_eq!(meta.arch, Architecture::Aarch64);
+        assert!(!meta.emulated);
+        assert_eq!(meta.install_mode, InstallMode::Standard);
+        assert_eq!(meta.created_at, None);
+        assert_eq!(SystemMeta::from_toml(&meta.to_toml()).unwrap().created_at, None);
+
+        assert!(SystemMeta::from_legacy("backend = lxc\n", "x", Architecture::Aarch64).is_err());
     }
-}
\ No newline at end of file
+}
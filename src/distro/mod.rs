@@ -2,8 +2,12 @@ pub mod name;
 pub mod meta;
 pub mod meta_serde;
 pub mod definitions;
+pub mod package_manager;
 
 pub use name::DistroName;
 pub use meta::SystemMeta;
+pub use package_manager::PackageManager;
 pub use definitions::distro_definition::DistroDefinition;
-pub use definitions::base::{get_all_distros, get_distro_by_name, get_distros_for_arch};
+pub use definitions::catalog::load_catalog;
+pub use definitions::search::{search_distros, DistroFilter};
+pub use definitions::base::{get_all_distros, get_distro_by_name, get_distros_for_arch, get_emulated_distros, package_manager_for};
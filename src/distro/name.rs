@@ -16,10 +16,45 @@ pub enum DistroName {
     Rockylinux,
     Alpine,
     Pardus,
+    /// A distro defined only in a user catalog, e.g. `[distro.mycorp]`.
+    Custom(String),
 }
 
 impl DistroName {
-    pub fn as_str(&self) -> &'static str {
+    /// The built-in distros; custom ones come from the catalog.
+    pub fn all() -> [DistroName; 14] {
+        [
+            DistroName::Adelie,
+            DistroName::Deepin,
+            DistroName::Debian,
+            DistroName::Chimera,
+            DistroName::Opensuse,
+            DistroName::Artix,
+            DistroName::Manjaro,
+            DistroName::Archlinux,
+            DistroName::Void,
+            DistroName::Fedora,
+            DistroName::Ubuntu,
+            DistroName::Rockylinux,
+            DistroName::Alpine,
+            DistroName::Pardus,
+        ]
+    }
+    
+    /// A built-in distro, or a custom one if `s` is a valid identifier:
+    /// lowercase letters, digits, `-` and `_`, starting with a letter.
+    pub fn from_str(s: &str) -> Option<Self> {
+        let s = s.to_lowercase();
+        if let Some(name) = Self::all().into_iter().find(|name| name.as_str() == s) {
+            return Some(name);
+        }
+        let valid = s.len() <= 32
+            && s.starts_with(|c: char| c.is_ascii_lowercase())
+            && s.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+        valid.then_some(DistroName::Custom(s))
+    }
+    
+    pub fn as_str(&self) -> &str {
         match self {
             DistroName::Adelie => "adelie",
             DistroName::Deepin => "deepin",
@@ -35,6 +70,7 @@ impl DistroName {
             DistroName::Rockylinux => "rockylinux",
             DistroName::Alpine => "alpine",
             DistroName::Pardus => "pardus",
+            DistroName::Custom(id) => id,
         }
     }
     
@@ -54,6 +90,7 @@ impl DistroName {
             DistroName::Rockylinux => "Rocky Linux".to_string(),
             DistroName::Alpine => "Alpine".to_string(),
             DistroName::Pardus => "Pardus".to_string(),
+            DistroName::Custom(id) => id.clone(),
         }
     }
 }
@@ -82,6 +119,16 @@ mod tests {
         assert_eq!(DistroName::Rockylinux.to_string(), "Rocky Linux");
     }
 
+    #[test]
+    fn test_distro_name_from_str() {
+        assert_eq!(DistroName::from_str("rockylinux"), Some(DistroName::Rockylinux));
+        assert_eq!(DistroName::from_str("Alpine"), Some(DistroName::Alpine));
+        assert_eq!(DistroName::from_str("MyCorp"), Some(DistroName::Custom("mycorp".to_string())));
+        assert_eq!(DistroName::from_str("my corp"), None);
+        assert_eq!(DistroName::from_str("../etc"), None);
+        assert_eq!(DistroName::from_str("1corp"), None);
+    }
+
     #[test]
     fn test_distro_name_display() {
         assert_eq!(format!("{}", DistroName::Alpine), "alpine");
@@ -0,0 +1,146 @@
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageManager {
+    Apt,
+    Pacman,
+    Dnf,
+    Yum,
+    Apk,
+    Zypper,
+    Xbps,
+}
+
+impl PackageManager {
+    /// Order matters: some rootfs ship `yum` as a wrapper around `dnf`.
+    const BINARIES: [(&'static str, PackageManager); 7] = [
+        ("usr/bin/apt-get", PackageManager::Apt),
+        ("usr/bin/pacman", PackageManager::Pacman),
+        ("usr/bin/dnf", PackageManager::Dnf),
+        ("usr/bin/yum", PackageManager::Yum),
+        ("sbin/apk", PackageManager::Apk),
+        ("usr/bin/zypper", PackageManager::Zypper),
+        ("usr/bin/xbps-install", PackageManager::Xbps),
+    ];
+
+    /// Detects the package manager from the binaries present in the rootfs.
+    pub fn detect(rootfs: &Path) -> Option<Self> {
+        Self::BINARIES
+            .iter()
+            .find(|(binary, _)| rootfs.join(binary).exists())
+            .map(|(_, manager)| *manager)
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        Self::BINARIES.iter().map(|(_, manager)| *manager).find(|manager| manager.as_str() == s)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PackageManager::Apt => "apt",
+            PackageManager::Pacman => "pacman",
+            PackageManager::Dnf => "dnf",
+            PackageManager::Yum => "yum",
+            PackageManager::Apk => "apk",
+            PackageManager::Zypper => "zypper",
+            PackageManager::Xbps => "xbps",
+        }
+    }
+
+    /// Non-interactive shell command refreshing the index and installing `packages`.
+    pub fn install_command(&self, packages: &[&str]) -> String {
+        let packages = packages.join(" ");
+        match self {
+            PackageManager::Apt => format!("apt-get update && DEBIAN_FRONTEND=noninteractive apt-get install -y {}", packages),
+            PackageManager::Pacman => format!("pacman -Sy --noconfirm --needed {}", packages),
+            PackageManager::Dnf => format!("dnf install -y {}", packages),
+            PackageManager::Yum => format!("yum install -y {}", packages),
+            PackageManager::Apk => format!("apk update && apk add {}", packages),
+            PackageManager::Zypper => format!("zypper --non-interactive install {}", packages),
+            PackageManager::Xbps => format!("xbps-install -Sy {}", packages),
+        }
+    }
+
+    /// Number of installed packages, read from the package database in the
+    /// rootfs without entering the guest. `None` for rpm-based systems, whose
+    /// database can only be read by rpm itself.
+    pub fn installed_count(&self, rootfs: &Path) -> Option<usize> {
+        match self {
+            PackageManager::Apt => {
+                let status = fs::read_to_string(rootfs.join("var/lib/dpkg/status")).ok()?;
+                Some(status.lines().filter(|line| line.trim() == "Status: install ok installed").count())
+            }
+            PackageManager::Pacman => {
+                let entries = fs::read_dir(rootfs.join("var/lib/pacman/local")).ok()?;
+                Some(entries.flatten().filter(|entry| entry.path().is_dir()).count())
+            }
+            PackageManager::Apk => {
+                let installed = fs::read_to_string(rootfs.join("lib/apk/db/installed")).ok()?;
+                Some(installed.lines().filter(|line| line.starts_with("P:")).count())
+            }
+            PackageManager::Xbps => {
+                let pkgdb = fs::read_dir(rootfs.join("var/db/xbps"))
+                    .ok()?
+                    .flatten()
+                    .map(|entry| entry.path())
+                    .find(|path| path.file_name().and_then(|n| n.to_str()).is_some_and(|n| n.starts_with("pkgdb-") && n.ends_with(".plist")))?;
+                let content = fs::read_to_string(pkgdb).ok()?;
+                Some(content.matches("<key>pkgver</key>").count())
+            }
+            PackageManager::Dnf | PackageManager::Yum | PackageManager::Zypper => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detect_package_manager() {
+        let temp_dir = TempDir::new().unwrap();
+        assert_eq!(PackageManager::detect(temp_dir.path()), None);
+
+        fs::create_dir_all(temp_dir.path().join("usr/bin")).unwrap();
+        fs::write(temp_dir.path().join("usr/bin/yum"), "").unwrap();
+        assert_eq!(PackageManager::detect(temp_dir.path()), Some(PackageManager::Yum));
+
+        fs::write(temp_dir.path().join("usr/bin/dnf"), "").unwrap();
+        assert_eq!(PackageManager::detect(temp_dir.path()), Some(PackageManager::Dnf));
+    }
+
+    #[test]
+    fn test_install_command() {
+        assert_eq!(
+            PackageManager::Apt.install_command(&["xfce4", "dbus-x11"]),
+            "apt-get update && DEBIAN_FRONTEND=noninteractive apt-get install -y xfce4 dbus-x11"
+        );
+        assert_eq!(PackageManager::Pacman.install_command(&["xfce4"]), "pacman -Sy --noconfirm --needed xfce4");
+    }
+
+    #[test]
+    fn test_package_manager_from_str() {
+        assert_eq!(PackageManager::from_str("xbps"), Some(PackageManager::Xbps));
+        assert_eq!(PackageManager::from_str("portage"), None);
+    }
+
+    #[test]
+    fn test_installed_count() {
+        let temp_dir = TempDir::new().unwrap();
+        assert_eq!(PackageManager::Apt.installed_count(temp_dir.path()), None);
+
+        fs::create_dir_all(temp_dir.path().join("var/lib/dpkg")).unwrap();
+        fs::write(
+            temp_dir.path().join("var/lib/dpkg/status"),
+            "Package: bash\nStatus: install ok installed\n\nPackage: old\nStatus: deinstall ok config-files\n\nPackage: coreutils\nStatus: install ok installed\n",
+        ).unwrap();
+        assert_eq!(PackageManager::Apt.installed_count(temp_dir.path()), Some(2));
+
+        fs::create_dir_all(temp_dir.path().join("var/lib/pacman/local/bash-5.2-1")).unwrap();
+        fs::write(temp_dir.path().join("var/lib/pacman/local/ALPM_DB_VERSION"), "9").unwrap();
+        assert_eq!(PackageManager::Pacman.installed_count(temp_dir.path()), Some(1));
+        assert_eq!(PackageManager::Dnf.installed_count(temp_dir.path()), None);
+    }
+}
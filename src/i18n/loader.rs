@@ -24,6 +24,13 @@ impl Language {
         }
     }
     
+    pub fn as_locale(&self) -> &'static str {
+        match self {
+            Language::Chinese => "zh_CN.UTF-8",
+            Language::English => "en_US.UTF-8",
+        }
+    }
+    
     #[allow(dead_code)]
     pub fn as_name(&self) -> &'static str {
         match self {
@@ -2,7 +2,6 @@ use std::collections::HashMap;
 use super::loader::Language;
 
 pub struct Translator {
-    #[allow(dead_code)]
     lang: Language,
     strings: HashMap<String, String>,
 }
@@ -30,7 +29,6 @@ impl Translator {
         result
     }
     
-    #[allow(dead_code)]
     pub fn get_language(&self) -> Language {
         self.lang
     }
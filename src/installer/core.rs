@@ -1,27 +1,60 @@
 use crate::distro::{DistroDefinition, SystemMeta};
-use crate::utils::arch::Architecture;
+use crate::distro::meta::InstallMode;
+use crate::utils::arch::{find_qemu_binary, Architecture};
 use crate::ui::{print_info, print_success, DownloadProgressBar, ExtractionProgressBar};
 use crate::i18n::Translator;
+use crate::system::launcher;
+use crate::system::backend::{backend_for, configured_backend};
 use std::path::PathBuf;
 
+/// How long the installer waits for a download's size.
+const CONTENT_LENGTH_TIMEOUT_SECS: u64 = 20;
+
+/// Where an install of `distro_def` downloads from.
+pub struct DownloadSource {
+    /// The catalog URL, or the `<distro>-link[.arch]` override.
+    pub url: String,
+    /// Catalog checksum; `None` for an override, which the catalog does not describe.
+    pub checksum: Option<String>,
+    /// `url` rewritten for `github_proxy`, then the direct URL.
+    pub candidates: Vec<String>,
+}
+
+/// Resolves the download the way `install` does; `None` when there is no
+/// URL for `arch`.
+pub fn download_source(
+    config_manager: &crate::config::ConfigManager,
+    distro_def: &DistroDefinition,
+    arch: &Architecture,
+) -> Result<Option<DownloadSource>, Box<dyn std::error::Error>> {
+    let link = config_manager.get_download_link_for_arch(distro_def.name.as_str(), arch.to_str())?;
+    let (url, checksum) = match (link, distro_def.get_url(arch)) {
+        (Some(link), _) => (link, None),
+        (None, Some(url)) => (url.clone(), distro_def.get_checksum(arch).cloned()),
+        (None, None) => return Ok(None),
+    };
+    let candidates = crate::utils::net::download_candidates(&url, config_manager.get_github_proxy()?.as_deref());
+    Ok(Some(DownloadSource { url, checksum, candidates }))
+}
+
 pub fn install_distro(
     distro_def: &DistroDefinition,
     custom_name: Option<String>,
     mode: &str,
     arch: &Architecture,
+    emulated: bool,
+    ignore_space: bool,
     translator: &Translator,
 ) -> Result<(), Box<dyn std::error::Error>> {
     print_info(&format!("Installing {}...", distro_def.display_name));
     
-    let url = match distro_def.get_url(&arch) {
-        Some(url) => url,
-        None => {
-            crate::ui::print_error(&format!("No URL found for architecture {}", arch.to_str()));
-            return Ok(());
-        }
+    let config_manager = crate::config::ConfigManager::new()?;
+    let Some(DownloadSource { url, checksum: expected_checksum, candidates }) = download_source(&config_manager, distro_def, arch)? else {
+        crate::ui::print_error(&format!("No URL found for architecture {}", arch.to_str()));
+        return Ok(());
     };
     
-    crate::ui::print_info(&format!("Download URL: {}", url));
+    crate::ui::print_info(&format!("Download URL: {}", candidates[0]));
     crate::ui::print_info(&format!("Default packages: {:?}", distro_def.default_packages));
     
     let home_dir = crate::utils::fs::get_home_dir()?;
@@ -40,30 +73,110 @@ pub fn install_distro(
         return Ok(());
     }
     
+    let backend_kind = configured_backend(&config_manager, &system_id)?;
+    let backend = backend_for(backend_kind);
+    if let Err(reason) = backend.check_available() {
+        crate::ui::print_error(&translator.t_fmt("backend_unavailable", &[backend.kind().as_str(), &reason]));
+        return Ok(());
+    }
+    
+    let emulator = if emulated {
+        if !backend.supports_emulation() {
+            crate::ui::print_error(&translator.t_fmt("emulation_requires_proot", &[backend.kind().as_str()]));
+            return Ok(());
+        }
+        match find_qemu_binary(arch) {
+            Some(path) => {
+                crate::ui::print_info(&translator.t_fmt("using_emulator", &[&path]));
+                Some(path)
+            }
+            None => {
+                crate::ui::print_error(&translator.t_fmt("qemu_not_found", &[arch.qemu_binary()]));
+                return Ok(());
+            }
+        }
+    } else {
+        None
+    };
+    
+    // Check before downloading anything, so a nearly full device fails here
+    // rather than halfway through extraction.
+    if ignore_space {
+        print_info(&translator.t("space_check_disabled"));
+    } else {
+        let download_size = candidates.iter().find_map(|candidate| crate::utils::net::get_content_length(candidate, CONTENT_LENGTH_TIMEOUT_SECS).ok().flatten());
+        match super::space::estimate_required_space(&url, download_size, distro_def.installed_size_mib) {
+            Some(required) => match crate::system::disk_usage::free_space(&termos_dir) {
+                Ok(available) => {
+                    let (required_text, available_text) = (crate::ui::format_size(required), crate::ui::format_size(available));
+                    if available < required {
+                        let dir = termos_dir.display().to_string();
+                        crate::ui::print_error(&translator.t_fmt("error_insufficient_space", &[&dir, &required_text, &available_text]));
+                        return Ok(());
+                    }
+                    print_info(&translator.t_fmt("space_required", &[&required_text, &available_text]));
+                }
+                Err(e) => crate::ui::print_error(&translator.t_fmt("space_check_failed", &[&e.to_string()])),
+            },
+            None => print_info(&translator.t("space_check_skipped")),
+        }
+    }
+    
     std::fs::create_dir_all(&system_dir)?;
     
     let archive_path = system_dir.join("rootfs.tar.xz");
     
     crate::ui::print_info(&translator.t("downloading"));
-    let mut download_progress = DownloadProgressBar::new(
-        translator.t("download_progress")
-    );
-    
-    if let Ok(Some(content_length)) = crate::utils::net::get_content_length(url) {
-        download_progress.set_total_size(content_length);
+    let mut url = &candidates[0];
+    for (index, candidate) in candidates.iter().enumerate() {
+        let mut download_progress = DownloadProgressBar::new(
+            translator.t("download_progress")
+        );
+        
+        if let Ok(Some(content_length)) = crate::utils::net::get_content_length(candidate, CONTENT_LENGTH_TIMEOUT_SECS) {
+            download_progress.set_total_size(content_length);
+        }
+        
+        let result = crate::utils::net::download_file_with_progress(
+            candidate,
+            archive_path.to_str().unwrap(),
+            |downloaded, _| {
+                download_progress.update(downloaded);
+            },
+        );
+        
+        match (result, candidates.get(index + 1)) {
+            (Ok(_), _) => {
+                download_progress.finish();
+                url = candidate;
+                break;
+            }
+            (Err(e), Some(next)) => {
+                crate::ui::print_error(&translator.t_fmt("download_fallback", &[&e.to_string(), next]));
+            }
+            (Err(e), None) => return Err(e),
+        }
     }
-    
-    crate::utils::net::download_file_with_progress(
-        url,
-        archive_path.to_str().unwrap(),
-        |downloaded, _| {
-            download_progress.update(downloaded);
-        },
-    )?;
-    
-    download_progress.finish();
     crate::ui::print_success(&translator.t("download_complete"));
     
+    let checksum = crate::utils::fs::file_sha256(&archive_path);
+    if let Some(expected) = &expected_checksum {
+        let actual = match &checksum {
+            Ok(checksum) => checksum.strip_prefix("sha256:").unwrap_or(checksum),
+            Err(e) => {
+                std::fs::remove_dir_all(&system_dir)?;
+                crate::ui::print_error(&translator.t_fmt("checksum_unavailable", &[&e.to_string()]));
+                return Ok(());
+            }
+        };
+        if actual != expected {
+            std::fs::remove_dir_all(&system_dir)?;
+            crate::ui::print_error(&translator.t_fmt("checksum_mismatch", &[expected, actual]));
+            return Ok(());
+        }
+        crate::ui::print_success(&translator.t("checksum_verified"));
+    }
+    
     crate::ui::print_info(&translator.t("extracting"));
     
     let total_files = crate::utils::fs::count_files_in_tar_xz(&archive_path)?;
@@ -75,6 +188,7 @@ pub fn install_distro(
     crate::utils::fs::extract_tar_xz_with_progress(
         &archive_path,
         &system_dir,
+        distro_def.strip_components,
         |extracted, file_name| {
             extract_progress.update(extracted, file_name);
         },
@@ -83,11 +197,26 @@ pub fn install_distro(
     extract_progress.finish();
     crate::ui::print_success(&translator.t("extraction_complete"));
     
-    let meta = SystemMeta::new(system_id.clone(), distro_def.name.to_string());
-    let meta_path = system_dir.join("meta.txt");
-    std::fs::write(&meta_path, meta.to_string())?;
+    let mut meta = SystemMeta::new(system_id.clone(), distro_def.name.as_str().to_string(), *arch);
+    meta.backend = backend_kind;
+    meta.emulated = emulated;
+    meta.install_mode = InstallMode::from_str(mode).unwrap_or_default();
+    meta.source_url = Some(url.to_string());
+    meta.release = distro_def.release.clone();
+    meta.checksum = checksum.ok();
+    meta.download_size = std::fs::metadata(&archive_path).ok().map(|m| m.len());
+    crate::utils::fs::save_system_meta(&system_id, &meta)?;
+    
+    if let Ok(mirror) = config_manager.get_mirror_for_distro(distro_def.name.as_str()) {
+        if let Some(manager) = crate::distro::package_manager_for(&system_dir, distro_def.name.as_str()) {
+            match crate::system::mirror::apply_mirror(&system_dir, manager, &mirror) {
+                Ok(0) => print_info(&translator.t_fmt("mirror_not_matched", &[&mirror])),
+                Ok(files) => crate::ui::print_success(&translator.t_fmt("mirror_applied", &[&mirror, &files.to_string()])),
+                Err(e) => crate::ui::print_error(&e.to_string()),
+            }
+        }
+    }
     
-    let config_manager = crate::config::ConfigManager::new()?;
     if let Some(init_commands) = config_manager.get_init_commands_for_distro(distro_def.name.as_str())? {
         crate::ui::print_info(&translator.t("executing_init_commands"));
         let commands: Vec<&str> = init_commands.lines().collect();
@@ -99,21 +228,21 @@ pub fn install_distro(
         }
     }
     
-    let start_script_path = system_dir.join("start.sh");
-    let shell_cmd = config_manager.get_shell_command()?.unwrap_or("/bin/bash --login".to_string());
-    let start_script_content = format!(
-        r#"#!/bin/bash
-cd "$(dirname "$0")"
-exec proot -r . -0 -w / -b /dev -b /proc -b /sys {}
-"#,
-        shell_cmd
-    );
-    std::fs::write(&start_script_path, start_script_content)?;
+    let locale = translator.get_language().as_locale();
+    let timezone = config_manager
+        .get_timezone_for_system(&system_id)?
+        .or_else(launcher::detect_host_timezone);
+    
+    crate::ui::print_info(&translator.t("configuring_locale"));
+    if launcher::configure_guest_locale(&system_dir, locale, timezone.as_deref())? {
+        let mut spec = launcher::build_launch_spec(&system_id, &system_dir, Vec::new(), &config_manager)?;
+        spec.emulator = emulator;
+        if let Err(e) = launcher::run_guest_command(backend.as_ref(), &spec, "locale-gen") {
+            crate::ui::print_error(&translator.t_fmt("locale_generation_failed", &[&e.to_string()]));
+        }
+    }
     
-    use std::os::unix::fs::PermissionsExt;
-    let mut perms = std::fs::metadata(&start_script_path)?.permissions();
-    perms.set_mode(0o755);
-    std::fs::set_permissions(&start_script_path, perms)?;
+    launcher::write_start_script(&system_dir, &system_id)?;
     
     Ok(())
 }
\ No newline at end of file
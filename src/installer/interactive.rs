@@ -1,34 +1,76 @@
 use std::io::{self, Write};
-use crate::distro::{get_distros_for_arch, SystemMeta};
-use crate::utils::arch::get_architecture;
-use crate::ui::{print_section, print_item, print_info, print_success};
+use crate::distro::{get_distros_for_arch, get_emulated_distros, load_catalog, DistroDefinition, DistroFilter};
+use crate::utils::arch::{get_architecture, get_emulated_architectures, Architecture};
+use crate::ui::{print_section, print_item, print_info, print_success, print_error};
 use crate::i18n::Translator;
 
 pub fn install_interactive(translator: &Translator) -> Result<(), Box<dyn std::error::Error>> {
-    let arch = get_architecture(translator)?;
-    let available_distros = get_distros_for_arch(&arch);
+    if let Err(e) = load_catalog() {
+        print_error(&translator.t_fmt("catalog_invalid", &[&e.to_string()]));
+    }
+    
+    let host_arch = get_architecture(translator)?;
+    let native_distros = get_distros_for_arch(&host_arch);
+    let emulated_distros = get_emulated_distros(&host_arch, &get_emulated_architectures(&host_arch));
     
-    if available_distros.is_empty() {
+    if native_distros.is_empty() && emulated_distros.is_empty() {
         print_info(&translator.t("no_distros_for_arch"));
         return Ok(());
     }
     
-    print_section(&translator.t("distro_selection"));
-    for (i, distro) in available_distros.iter().enumerate() {
-        print_item(&format!("{}.", i + 1), &distro.display_name);
+    // Every release of a distro gets its own entry, default release first.
+    let mut choices: Vec<(DistroDefinition, Architecture, bool)> = Vec::new();
+    for distro in native_distros.iter().flat_map(|d| d.variants()).filter(|d| d.supports_arch(&host_arch)) {
+        choices.push((distro, host_arch, false));
+    }
+    for (distro, arch) in &emulated_distros {
+        for variant in distro.variants().into_iter().filter(|d| d.supports_arch(arch)) {
+            choices.push((variant, *arch, true));
+        }
     }
     
-    print!("\n{}", translator.t("enter_number_select"));
-    io::stdout().flush().unwrap();
-    
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).unwrap();
-    
-    let selected_distro = match input.trim().parse::<usize>() {
-        Ok(num) if num > 0 && num <= available_distros.len() => &available_distros[num - 1],
-        _ => {
-            println!("\n{}", translator.t("invalid_choice"));
-            return Ok(());
+    // Typing text instead of a number narrows the menu down; an empty line
+    // shows everything again.
+    let mut filter = DistroFilter::default();
+    let (selected_distro, arch, emulated) = loop {
+        let visible: Vec<usize> = (0..choices.len()).filter(|&i| filter.matches(&choices[i].0)).collect();
+        
+        print_section(&translator.t("distro_selection"));
+        for (number, &index) in visible.iter().enumerate() {
+            let (distro, arch, emulated) = &choices[index];
+            if *emulated && (number == 0 || !choices[visible[number - 1]].2) {
+                print_section(&translator.t("emulated_distros_section"));
+            }
+            let label = if *emulated { format!("{} ({})", distro.label(), arch.to_str()) } else { distro.label() };
+            print_item(&format!("{}.", number + 1), &label);
+        }
+        
+        print!("\n{}", translator.t("enter_number_or_filter"));
+        io::stdout().flush().unwrap();
+        
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).unwrap();
+        let input = input.trim();
+        
+        match input.parse::<usize>() {
+            Ok(num) if num > 0 && num <= visible.len() => break choices.swap_remove(visible[num - 1]),
+            Ok(_) => {
+                println!("\n{}", translator.t("invalid_choice"));
+                return Ok(());
+            }
+            Err(_) if input.is_empty() && filter.term.is_none() => {
+                println!("\n{}", translator.t("invalid_choice"));
+                return Ok(());
+            }
+            Err(_) if input.is_empty() => filter.term = None,
+            Err(_) => {
+                let narrowed = DistroFilter { term: Some(input.to_string()), ..Default::default() };
+                if choices.iter().any(|(distro, _, _)| narrowed.matches(distro)) {
+                    filter = narrowed;
+                } else {
+                    print_info(&translator.t_fmt("no_distros_matched_term", &[input]));
+                }
+            }
         }
     };
     
@@ -69,7 +111,7 @@ pub fn install_interactive(translator: &Translator) -> Result<(), Box<dyn std::e
         print_info(&translator.t("starting_standard"));
     }
     
-    super::core::install_distro(selected_distro, custom_name.clone(), mode, &arch, translator)?;
+    super::core::install_distro(&selected_distro, custom_name.clone(), mode, &arch, emulated, false, translator)?;
     
     print_success(&translator.t("install_complete_exclamation"));
     
@@ -88,19 +130,8 @@ pub fn install_interactive(translator: &Translator) -> Result<(), Box<dyn std::e
             format!("{}{}", selected_distro.name, 1)
         };
         
-        let home_dir = crate::utils::fs::get_home_dir()?;
-        let system_path = home_dir.join("termos").join(&system_id);
-        
-        if system_path.exists() {
-            std::env::set_current_dir(&system_path)?;
-            
-            if std::path::Path::new("./start.sh").exists() {
-                crate::utils::cmd::run_command("./start.sh")?;
-            } else {
-                crate::ui::print_error(&translator.t("start_script_not_found"));
-            }
-        } else {
-            crate::ui::print_error(&translator.t_fmt("system_path_not_found", &[&system_id]));
+        if let Err(e) = crate::system::launcher::login_system(&system_id, translator) {
+            crate::ui::print_error(&e.to_string());
         }
     }
     
@@ -1,5 +1,6 @@
 pub mod interactive;
 pub mod core;
+pub mod space;
 
 use crate::i18n::Translator;
 
@@ -0,0 +1,36 @@
+/// How much an archive grows when unpacked, when the catalog gives no
+/// `installed_size_mib`. Rootfs tarballs hold mostly binaries and text.
+fn expansion_ratio(url: &str) -> u64 {
+    let file = url.rsplit('/').next().unwrap_or(url);
+    if file.ends_with(".xz") || file.ends_with(".txz") || file.ends_with(".zst") {
+        4
+    } else {
+        3
+    }
+}
+
+/// Space an install needs: the archive, which stays in the system directory,
+/// plus the extracted rootfs. `None` when neither size is known.
+pub fn estimate_required_space(url: &str, download_size: Option<u64>, installed_size_mib: Option<u64>) -> Option<u64> {
+    let extracted = match (installed_size_mib, download_size) {
+        (Some(mib), _) => mib * 1024 * 1024,
+        (None, Some(download)) => download * expansion_ratio(url),
+        (None, None) => return None,
+    };
+    Some(download_size.unwrap_or(0) + extracted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_required_space() {
+        let mib = 1024 * 1024;
+        assert_eq!(estimate_required_space("https://a/debian.tar.xz", Some(30 * mib), None), Some(150 * mib));
+        assert_eq!(estimate_required_space("https://a/rootfs.tar.gz", Some(30 * mib), None), Some(120 * mib));
+        assert_eq!(estimate_required_space("https://a/debian.tar.xz", Some(30 * mib), Some(400)), Some(430 * mib));
+        assert_eq!(estimate_required_space("https://a/debian.tar.xz", None, Some(400)), Some(400 * mib));
+        assert_eq!(estimate_required_space("https://a/debian.tar.xz", None, None), None);
+    }
+}
@@ -0,0 +1,72 @@
+use crate::config::ConfigManager;
+use crate::utils::cmd::{run_command, run_command_with_output};
+
+pub const PULSE_TCP_MODULE: &str = "module-native-protocol-tcp";
+pub const PULSE_TCP_ARGS: &str = "auth-ip-acl=127.0.0.1 auth-anonymous=1";
+pub const PULSE_SERVER: &str = "tcp:127.0.0.1:4713";
+
+#[derive(Debug, PartialEq)]
+pub enum AudioBridge {
+    Ready,
+    /// `pulseaudio` is not installed on the host.
+    Unavailable,
+}
+
+/// `<system_id>.audio = true` (or the global `audio`) opts a system in.
+pub fn is_audio_enabled(config_manager: &ConfigManager, system_id: &str) -> Result<bool, Box<dyn std::error::Error>> {
+    let value = config_manager.get_system_setting(system_id, "audio")?.unwrap_or_default();
+    Ok(matches!(value.to_lowercase().as_str(), "true" | "yes" | "on" | "1"))
+}
+
+fn has_tcp_module(modules: &str) -> bool {
+    modules
+        .lines()
+        .any(|line| line.split_whitespace().nth(1) == Some(PULSE_TCP_MODULE))
+}
+
+/// Starts the host PulseAudio daemon with the TCP module, or loads the
+/// module into an already running daemon.
+pub fn ensure_pulse_tcp() -> Result<AudioBridge, Box<dyn std::error::Error>> {
+    if run_command_with_output("command -v pulseaudio").is_err() {
+        return Ok(AudioBridge::Unavailable);
+    }
+
+    if run_command("pulseaudio --check").is_err() {
+        run_command(&format!(
+            "pulseaudio --start --exit-idle-time=-1 --load=\"{} {}\"",
+            PULSE_TCP_MODULE, PULSE_TCP_ARGS
+        ))?;
+        return Ok(AudioBridge::Ready);
+    }
+
+    let modules = run_command_with_output("pactl list short modules")?;
+    if !has_tcp_module(&modules) {
+        run_command(&format!("pactl load-module {} {}", PULSE_TCP_MODULE, PULSE_TCP_ARGS))?;
+    }
+    Ok(AudioBridge::Ready)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_has_tcp_module() {
+        let modules = "0\tmodule-device-restore\t\t\n7\tmodule-native-protocol-tcp\tauth-anonymous=1\t\n";
+        assert!(has_tcp_module(modules));
+        assert!(!has_tcp_module("0\tmodule-native-protocol-unix\t\t\n"));
+    }
+
+    #[test]
+    fn test_is_audio_enabled() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("config"), "debian1.audio = true\narch1.audio = no\n").unwrap();
+        let config_manager = ConfigManager::with_dir(temp_dir.path());
+
+        assert!(is_audio_enabled(&config_manager, "debian1").unwrap());
+        assert!(!is_audio_enabled(&config_manager, "arch1").unwrap());
+        assert!(!is_audio_enabled(&config_manager, "fedora1").unwrap());
+    }
+}
@@ -0,0 +1,105 @@
+use super::{BackendKind, ContainerBackend, LaunchSpec};
+use crate::utils::cmd::{check_command_exists, shell_quote};
+use crate::utils::permissions::is_root_user;
+
+pub struct ChrootBackend;
+
+impl ChrootBackend {
+    /// `(host source, guest target)` pairs in mount order; `None` means a fresh procfs.
+    fn mounts(spec: &LaunchSpec) -> Vec<(Option<String>, String)> {
+        let mut mounts = vec![
+            (Some("/dev".to_string()), "/dev".to_string()),
+            (Some("/dev/pts".to_string()), "/dev/pts".to_string()),
+            (None, "/proc".to_string()),
+            (Some("/sys".to_string()), "/sys".to_string()),
+        ];
+        for (host, guest) in &spec.binds {
+            mounts.push((Some(host.clone()), guest.clone()));
+        }
+        mounts
+    }
+}
+
+impl ContainerBackend for ChrootBackend {
+    fn kind(&self) -> BackendKind {
+        BackendKind::Chroot
+    }
+    
+    fn check_available(&self) -> Result<(), String> {
+        if !is_root_user() {
+            return Err("chroot 后端需要 root 权限".to_string());
+        }
+        if !check_command_exists("chroot") {
+            return Err("未找到 chroot 命令".to_string());
+        }
+        Ok(())
+    }
+    
+    fn setup_commands(&self, spec: &LaunchSpec) -> Vec<String> {
+        Self::mounts(spec)
+            .into_iter()
+            .map(|(source, guest)| {
+                let target = shell_quote(&spec.guest_path(&guest));
+                let mount = match source {
+                    Some(host) => format!("mount --bind {} {}", shell_quote(&host), target),
+                    None => format!("mount -t proc proc {}", target),
+                };
+                format!("mkdir -p {target} && (mountpoint -q {target} || {mount})", target = target, mount = mount)
+            })
+            .collect()
+    }
+    
+    fn command_line(&self, spec: &LaunchSpec) -> Vec<String> {
+        let mut args = vec!["chroot".to_string(), spec.rootfs_str()];
+        args.extend(spec.guest_exec_args());
+        args
+    }
+    
+    fn teardown_commands(&self, spec: &LaunchSpec) -> Vec<String> {
+        Self::mounts(spec)
+            .into_iter()
+            .rev()
+            .map(|(_, guest)| {
+                let target = shell_quote(&spec.guest_path(&guest));
+                format!("! mountpoint -q {target} || umount -l {target}", target = target)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn spec() -> LaunchSpec {
+        LaunchSpec {
+            rootfs: PathBuf::from("/termos/debian1"),
+            env: Vec::new(),
+            workdir: "/root".to_string(),
+            binds: vec![("/sdcard".to_string(), "/mnt/sdcard".to_string())],
+            argv: vec!["/bin/bash".to_string()],
+            emulator: None,
+        }
+    }
+
+    #[test]
+    fn test_chroot_mounts_and_cleanup_are_symmetric() {
+        let setup = ChrootBackend.setup_commands(&spec());
+        let teardown = ChrootBackend.teardown_commands(&spec());
+        
+        assert_eq!(setup.len(), 5);
+        assert_eq!(teardown.len(), 5);
+        assert!(setup[0].contains("mount --bind '/dev' '/termos/debian1/dev'"));
+        assert!(setup[2].contains("mount -t proc proc '/termos/debian1/proc'"));
+        assert!(teardown[0].contains("umount -l '/termos/debian1/mnt/sdcard'"));
+        assert!(teardown[4].contains("umount -l '/termos/debian1/dev'"));
+    }
+
+    #[test]
+    fn test_chroot_command_line() {
+        let line = ChrootBackend.command_line(&spec());
+        assert_eq!(&line[..4], &["chroot", "/termos/debian1", "/usr/bin/env", "-i"]);
+        assert_eq!(line.last().unwrap(), "/bin/bash");
+    }
+}
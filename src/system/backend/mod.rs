@@ -0,0 +1,230 @@
+pub mod proot;
+pub mod chroot;
+pub mod unshare;
+
+use std::fmt;
+use std::fs::OpenOptions;
+use std::os::unix::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use crate::config::ConfigManager;
+use crate::system::services::has_running_services;
+use crate::system::session::{is_system_running, record_session};
+use crate::utils::cmd::{run_command, shell_quote};
+use crate::utils::process::{ignore_terminal_signals, restore_terminal_signals};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BackendKind {
+    #[default]
+    Proot,
+    Chroot,
+    Unshare,
+}
+
+impl BackendKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BackendKind::Proot => "proot",
+            BackendKind::Chroot => "chroot",
+            BackendKind::Unshare => "unshare",
+        }
+    }
+    
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "proot" => Some(BackendKind::Proot),
+            "chroot" => Some(BackendKind::Chroot),
+            "unshare" | "userns" => Some(BackendKind::Unshare),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for BackendKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Everything a backend needs to enter a rootfs and run `argv` there.
+#[derive(Debug, Clone)]
+pub struct LaunchSpec {
+    pub rootfs: PathBuf,
+    pub env: Vec<(String, String)>,
+    pub workdir: String,
+    /// Extra `(host, guest)` bind mounts on top of the backend's own `/dev`, `/proc`, `/sys`.
+    pub binds: Vec<(String, String)>,
+    pub argv: Vec<String>,
+    /// `qemu-<arch>` binary for foreign-architecture systems.
+    pub emulator: Option<String>,
+}
+
+impl LaunchSpec {
+    pub fn rootfs_str(&self) -> String {
+        self.rootfs.to_string_lossy().to_string()
+    }
+    
+    pub fn guest_path(&self, guest: &str) -> String {
+        format!("{}/{}", self.rootfs_str().trim_end_matches('/'), guest.trim_start_matches('/'))
+    }
+    
+    /// `env -i K=V ... sh -c 'cd workdir; exec "$@"' sh argv...`, shared by the
+    /// backends that have no working-directory option of their own.
+    pub fn guest_exec_args(&self) -> Vec<String> {
+        let mut args = vec!["/usr/bin/env".to_string(), "-i".to_string()];
+        args.extend(self.env.iter().map(|(name, value)| format!("{}={}", name, value)));
+        args.push("/bin/sh".to_string());
+        args.push("-c".to_string());
+        args.push(format!("cd {} 2>/dev/null; exec \"$@\"", shell_quote(&self.workdir)));
+        args.push("sh".to_string());
+        args.extend(self.argv.iter().cloned());
+        args
+    }
+}
+
+pub trait ContainerBackend {
+    fn kind(&self) -> BackendKind;
+    
+    fn supports_emulation(&self) -> bool {
+        false
+    }
+    
+    /// Returns the reason the backend cannot be used on this host, if any.
+    fn check_available(&self) -> Result<(), String>;
+    
+    /// Shell commands run on the host before entering the rootfs.
+    fn setup_commands(&self, _spec: &LaunchSpec) -> Vec<String> {
+        Vec::new()
+    }
+    
+    fn command_line(&self, spec: &LaunchSpec) -> Vec<String>;
+    
+    /// Shell commands run on the host after the guest process exits.
+    fn teardown_commands(&self, _spec: &LaunchSpec) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+pub fn backend_for(kind: BackendKind) -> Box<dyn ContainerBackend> {
+    match kind {
+        BackendKind::Proot => Box::new(proot::ProotBackend),
+        BackendKind::Chroot => Box::new(chroot::ChrootBackend),
+        BackendKind::Unshare => Box::new(unshare::UnshareBackend),
+    }
+}
+
+/// Reads `<system_id>.backend` (or the global `backend`) from the config.
+pub fn configured_backend(config_manager: &ConfigManager, system_id: &str) -> Result<BackendKind, Box<dyn std::error::Error>> {
+    match config_manager.get_system_setting(system_id, "backend")? {
+        Some(value) => BackendKind::from_str(&value)
+            .ok_or_else(|| format!("未知的容器后端: {}", value).into()),
+        None => Ok(BackendKind::default()),
+    }
+}
+
+pub fn build_command(backend: &dyn ContainerBackend, spec: &LaunchSpec) -> Result<Command, Box<dyn std::error::Error>> {
+    let argv = backend.command_line(spec);
+    let (program, args) = argv.split_first().ok_or("Empty backend command line")?;
+    
+    let mut command = Command::new(program);
+    command.args(args).env_remove("LD_PRELOAD");
+    Ok(command)
+}
+
+fn check_launch(backend: &dyn ContainerBackend, spec: &LaunchSpec) -> Result<(), Box<dyn std::error::Error>> {
+    backend.check_available()?;
+    if spec.emulator.is_some() && !backend.supports_emulation() {
+        return Err(format!("{} 后端不支持 qemu 模拟，请使用 proot", backend.kind()).into());
+    }
+    
+    for cmd in backend.setup_commands(spec) {
+        if let Err(e) = run_command(&cmd) {
+            teardown(backend, spec);
+            return Err(e);
+        }
+    }
+    
+    Ok(())
+}
+
+/// Runs the guest process in the foreground as a recorded session and
+/// performs the backend's teardown once no other session of the same rootfs
+/// is left, returning the guest's exit code.
+pub fn launch(backend: &dyn ContainerBackend, spec: &LaunchSpec) -> Result<i32, Box<dyn std::error::Error>> {
+    check_launch(backend, spec)?;
+    
+    // Held through teardown: Ctrl-C in the guest must not take insOs down
+    // with it and leave the mounts behind.
+    let _signals = ignore_terminal_signals();
+    let result = build_command(backend, spec).and_then(|mut command| {
+        restore_terminal_signals(&mut command);
+        let mut child = command.spawn()?;
+        let _session = record_session(&spec.rootfs, child.id(), &spec.argv).ok();
+        Ok(child.wait()?)
+    });
+    teardown(backend, spec);
+    
+    Ok(result?.code().unwrap_or(1))
+}
+
+/// Starts the guest process in its own process group with output appended to
+/// `log_path`, without waiting for it. Mounts stay in place until `teardown`.
+pub fn spawn_detached(backend: &dyn ContainerBackend, spec: &LaunchSpec, log_path: &Path) -> Result<Child, Box<dyn std::error::Error>> {
+    check_launch(backend, spec)?;
+    
+    if let Some(parent) = log_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let log = OpenOptions::new().create(true).append(true).open(log_path)?;
+    
+    let mut command = build_command(backend, spec)?;
+    command
+        .stdin(Stdio::null())
+        .stdout(log.try_clone()?)
+        .stderr(Stdio::from(log))
+        .process_group(0);
+    Ok(command.spawn()?)
+}
+
+/// Runs the backend's teardown unless sessions or services still use the rootfs.
+pub fn teardown(backend: &dyn ContainerBackend, spec: &LaunchSpec) {
+    if is_system_running(&spec.rootfs) || has_running_services(&spec.rootfs) {
+        return;
+    }
+    
+    for cmd in backend.teardown_commands(spec) {
+        let _ = run_command(&cmd);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backend_kind_from_str() {
+        assert_eq!(BackendKind::from_str("proot"), Some(BackendKind::Proot));
+        assert_eq!(BackendKind::from_str("CHROOT"), Some(BackendKind::Chroot));
+        assert_eq!(BackendKind::from_str("userns"), Some(BackendKind::Unshare));
+        assert_eq!(BackendKind::from_str("docker"), None);
+        assert_eq!(BackendKind::default(), BackendKind::Proot);
+    }
+
+    #[test]
+    fn test_guest_exec_args() {
+        let spec = LaunchSpec {
+            rootfs: PathBuf::from("/data/termos/debian1"),
+            env: vec![("HOME".to_string(), "/root".to_string())],
+            workdir: "/root".to_string(),
+            binds: Vec::new(),
+            argv: vec!["/bin/bash".to_string(), "--login".to_string()],
+            emulator: None,
+        };
+        
+        assert_eq!(spec.guest_path("/dev/pts"), "/data/termos/debian1/dev/pts");
+        assert_eq!(spec.guest_exec_args(), vec![
+            "/usr/bin/env", "-i", "HOME=/root", "/bin/sh", "-c",
+            "cd '/root' 2>/dev/null; exec \"$@\"", "sh", "/bin/bash", "--login",
+        ]);
+    }
+}
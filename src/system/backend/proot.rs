@@ -0,0 +1,94 @@
+use super::{BackendKind, ContainerBackend, LaunchSpec};
+use crate::utils::cmd::check_command_exists;
+
+pub struct ProotBackend;
+
+impl ContainerBackend for ProotBackend {
+    fn kind(&self) -> BackendKind {
+        BackendKind::Proot
+    }
+    
+    fn supports_emulation(&self) -> bool {
+        true
+    }
+    
+    fn check_available(&self) -> Result<(), String> {
+        if check_command_exists("proot") {
+            Ok(())
+        } else {
+            Err("proot 未安装，请执行 pkg install proot".to_string())
+        }
+    }
+    
+    fn command_line(&self, spec: &LaunchSpec) -> Vec<String> {
+        let mut args: Vec<String> = vec![
+            "proot".to_string(),
+            "-r".to_string(),
+            spec.rootfs_str(),
+            "-0".to_string(),
+            "-w".to_string(),
+            spec.workdir.clone(),
+        ];
+        
+        if let Some(emulator) = &spec.emulator {
+            args.push("-q".to_string());
+            args.push(emulator.clone());
+        }
+        
+        for path in ["/dev", "/proc", "/sys"] {
+            args.push("-b".to_string());
+            args.push(path.to_string());
+        }
+        for (host, guest) in &spec.binds {
+            args.push("-b".to_string());
+            args.push(format!("{}:{}", host, guest));
+        }
+        
+        args.push("/usr/bin/env".to_string());
+        args.push("-i".to_string());
+        args.extend(spec.env.iter().map(|(name, value)| format!("{}={}", name, value)));
+        args.extend(spec.argv.iter().cloned());
+        args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_proot_command_line() {
+        let spec = LaunchSpec {
+            rootfs: PathBuf::from("/termos/debian1"),
+            env: vec![("HOME".to_string(), "/root".to_string())],
+            workdir: "/root".to_string(),
+            binds: vec![("/sdcard".to_string(), "/mnt/sdcard".to_string())],
+            argv: vec!["/bin/bash".to_string(), "--login".to_string()],
+            emulator: None,
+        };
+        
+        let line = ProotBackend.command_line(&spec).join(" ");
+        assert_eq!(
+            line,
+            "proot -r /termos/debian1 -0 -w /root -b /dev -b /proc -b /sys -b /sdcard:/mnt/sdcard /usr/bin/env -i HOME=/root /bin/bash --login"
+        );
+        assert!(ProotBackend.setup_commands(&spec).is_empty());
+    }
+
+    #[test]
+    fn test_proot_command_line_with_emulator() {
+        let spec = LaunchSpec {
+            rootfs: PathBuf::from("/termos/fedora1"),
+            env: Vec::new(),
+            workdir: "/root".to_string(),
+            binds: Vec::new(),
+            argv: vec!["/bin/sh".to_string()],
+            emulator: Some("/usr/bin/qemu-x86_64".to_string()),
+        };
+        
+        let line = ProotBackend.command_line(&spec);
+        assert_eq!(&line[6..8], &["-q", "/usr/bin/qemu-x86_64"]);
+        assert!(ProotBackend.supports_emulation());
+    }
+}
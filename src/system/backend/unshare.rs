@@ -0,0 +1,77 @@
+use super::{BackendKind, ContainerBackend, LaunchSpec};
+use crate::utils::cmd::{check_command_exists, shell_quote};
+
+/// Rootless containers for ordinary Linux hosts: a user namespace maps the
+/// caller to root and the mounts disappear together with the namespace.
+pub struct UnshareBackend;
+
+impl ContainerBackend for UnshareBackend {
+    fn kind(&self) -> BackendKind {
+        BackendKind::Unshare
+    }
+    
+    fn check_available(&self) -> Result<(), String> {
+        if !check_command_exists("unshare") || !check_command_exists("chroot") {
+            return Err("未找到 unshare/chroot 命令（util-linux）".to_string());
+        }
+        Ok(())
+    }
+    
+    fn command_line(&self, spec: &LaunchSpec) -> Vec<String> {
+        let mut steps = Vec::new();
+        let mut binds = vec![
+            ("/dev".to_string(), "/dev".to_string()),
+            ("/sys".to_string(), "/sys".to_string()),
+        ];
+        binds.extend(spec.binds.iter().cloned());
+        
+        for (host, guest) in &binds {
+            let target = shell_quote(&spec.guest_path(guest));
+            steps.push(format!("mkdir -p {} && mount --rbind {} {}", target, shell_quote(host), target));
+        }
+        
+        let exec_args: Vec<String> = spec.guest_exec_args().iter().map(|arg| shell_quote(arg)).collect();
+        steps.push(format!("exec chroot {} {}", shell_quote(&spec.rootfs_str()), exec_args.join(" ")));
+        
+        vec![
+            "unshare".to_string(),
+            "--user".to_string(),
+            "--map-root-user".to_string(),
+            "--mount".to_string(),
+            "--pid".to_string(),
+            "--fork".to_string(),
+            format!("--mount-proc={}", spec.guest_path("/proc")),
+            "/bin/sh".to_string(),
+            "-c".to_string(),
+            steps.join(" && "),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_unshare_command_line() {
+        let spec = LaunchSpec {
+            rootfs: PathBuf::from("/termos/alpine1"),
+            env: vec![("HOME".to_string(), "/root".to_string())],
+            workdir: "/root".to_string(),
+            binds: Vec::new(),
+            argv: vec!["/bin/sh".to_string()],
+            emulator: None,
+        };
+        
+        let line = UnshareBackend.command_line(&spec);
+        assert_eq!(line[0], "unshare");
+        assert!(line.contains(&"--map-root-user".to_string()));
+        assert!(line.contains(&"--mount-proc=/termos/alpine1/proc".to_string()));
+        
+        let script = line.last().unwrap();
+        assert!(script.starts_with("mkdir -p '/termos/alpine1/dev' && mount --rbind '/dev' '/termos/alpine1/dev'"));
+        assert!(script.contains("exec chroot '/termos/alpine1' '/usr/bin/env' '-i' 'HOME=/root'"));
+        assert!(UnshareBackend.teardown_commands(&spec).is_empty());
+    }
+}
@@ -0,0 +1,90 @@
+use std::path::Path;
+use crate::config::ConfigManager;
+use crate::i18n::Translator;
+use crate::system::check_items::{
+    check_config_file, check_directory, check_executable, check_launcher, check_metadata, guest_command_path, has_localhost, has_nameserver,
+};
+use crate::system::info::read_os_release;
+use crate::system::launcher::{DEFAULT_SHELL, GUEST_HOME};
+use crate::utils::fs::get_system_dir;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckKind {
+    Metadata,
+    Launcher,
+    Executable,
+    OsRelease,
+    TmpDir,
+    HomeDir,
+    Resolver,
+    Hosts,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Problem {
+    Missing,
+    NotExecutable,
+    Mode { expected: u32, actual: u32 },
+    Invalid(String),
+    Outdated,
+    NoNameserver,
+    NoLocalhost,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckItem {
+    pub kind: CheckKind,
+    /// Guest path or file name shown to the user.
+    pub subject: String,
+    pub problem: Option<Problem>,
+}
+
+impl CheckItem {
+    pub(super) fn new(kind: CheckKind, subject: &str, problem: Option<Problem>) -> Self {
+        Self { kind, subject: subject.to_string(), problem }
+    }
+
+    /// Whether `insOs repair` can fix the problem without touching user data.
+    pub fn is_repairable(&self) -> bool {
+        !matches!(
+            (&self.problem, self.kind),
+            (None, _) | (Some(_), CheckKind::OsRelease) | (Some(Problem::Missing), CheckKind::Executable)
+        )
+    }
+}
+
+pub fn check_rootfs(system_dir: &Path, system_id: &str, shell_command: &str) -> Vec<CheckItem> {
+    let mut items = vec![
+        check_metadata(system_dir),
+        check_launcher(system_dir, system_id),
+        check_executable(system_dir, "/bin/sh"),
+    ];
+
+    let shell = shell_command.split_whitespace().next().unwrap_or("/bin/sh");
+    let shell_path = guest_command_path(system_dir, shell);
+    if shell_path != "/bin/sh" {
+        items.push(check_executable(system_dir, &shell_path));
+    }
+
+    let os_release = read_os_release(system_dir).is_some();
+    items.push(CheckItem::new(CheckKind::OsRelease, "/etc/os-release", (!os_release).then_some(Problem::Missing)));
+    items.push(check_directory(system_dir, CheckKind::TmpDir, "/tmp", Some(0o1777)));
+    items.push(check_directory(system_dir, CheckKind::HomeDir, GUEST_HOME, None));
+    items.push(check_config_file(system_dir, CheckKind::Resolver, "/etc/resolv.conf", has_nameserver, Problem::NoNameserver));
+    items.push(check_config_file(system_dir, CheckKind::Hosts, "/etc/hosts", has_localhost, Problem::NoLocalhost));
+    items
+}
+
+pub fn check_system(system_id: &str, translator: &Translator) -> Result<Vec<CheckItem>, Box<dyn std::error::Error>> {
+    let system_dir = get_system_dir(system_id)?;
+    if !system_dir.exists() {
+        return Err(translator.t_fmt("system_not_exist", &[system_id]).into());
+    }
+
+    let shell = ConfigManager::new()?.get_shell_command()?.unwrap_or_else(|| DEFAULT_SHELL.to_string());
+    Ok(check_rootfs(&system_dir, system_id, &shell))
+}
+
+#[cfg(test)]
+#[path = "check_tests.rs"]
+mod check_tests;
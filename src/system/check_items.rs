@@ -0,0 +1,85 @@
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use crate::distro::meta::{LEGACY_META_FILE, META_FILE};
+use crate::distro::SystemMeta;
+use crate::system::check::{CheckItem, CheckKind, Problem};
+use crate::system::launcher::{current_insos_path, render_start_script, GUEST_PATH};
+use crate::utils::fs::resolve_guest_path;
+
+pub(super) fn check_metadata(system_dir: &Path) -> CheckItem {
+    let problem = match fs::read_to_string(system_dir.join(META_FILE)) {
+        Ok(content) => SystemMeta::from_toml(&content).err().map(|e| Problem::Invalid(e.to_string())),
+        Err(_) if system_dir.join(LEGACY_META_FILE).exists() => Some(Problem::Outdated),
+        Err(_) => Some(Problem::Missing),
+    };
+    CheckItem::new(CheckKind::Metadata, META_FILE, problem)
+}
+
+pub(super) fn check_launcher(system_dir: &Path, system_id: &str) -> CheckItem {
+    let path = system_dir.join("start.sh");
+    let problem = match fs::read_to_string(&path) {
+        Err(_) => Some(Problem::Missing),
+        Ok(content) if content != render_start_script(system_id, &current_insos_path()) => Some(Problem::Outdated),
+        Ok(_) if !is_executable(&path) => Some(Problem::NotExecutable),
+        Ok(_) => None,
+    };
+    CheckItem::new(CheckKind::Launcher, "start.sh", problem)
+}
+
+fn is_executable(path: &Path) -> bool {
+    fs::metadata(path).map(|meta| meta.permissions().mode() & 0o111 != 0).unwrap_or(false)
+}
+
+/// The guest path of a command: absolute paths as-is, bare names looked up
+/// in the guest's `PATH`.
+pub(super) fn guest_command_path(rootfs: &Path, command: &str) -> String {
+    if command.starts_with('/') {
+        return command.to_string();
+    }
+    GUEST_PATH
+        .split(':')
+        .map(|dir| format!("{}/{}", dir, command))
+        .find(|path| resolve_guest_path(rootfs, path).is_some())
+        .unwrap_or_else(|| format!("/usr/bin/{}", command))
+}
+
+pub(super) fn check_executable(rootfs: &Path, guest_path: &str) -> CheckItem {
+    let problem = match resolve_guest_path(rootfs, guest_path) {
+        None => Some(Problem::Missing),
+        Some(path) if !is_executable(&path) => Some(Problem::NotExecutable),
+        Some(_) => None,
+    };
+    CheckItem::new(CheckKind::Executable, guest_path, problem)
+}
+
+pub(super) fn check_directory(rootfs: &Path, kind: CheckKind, guest_path: &str, expected_mode: Option<u32>) -> CheckItem {
+    let problem = match resolve_guest_path(rootfs, guest_path).and_then(|path| fs::metadata(path).ok()) {
+        Some(meta) if meta.is_dir() => {
+            let actual = meta.permissions().mode() & 0o7777;
+            expected_mode.filter(|expected| *expected != actual).map(|expected| Problem::Mode { expected, actual })
+        }
+        _ => Some(Problem::Missing),
+    };
+    CheckItem::new(kind, guest_path, problem)
+}
+
+pub(super) fn check_config_file(rootfs: &Path, kind: CheckKind, guest_path: &str, valid: impl Fn(&str) -> bool, problem: Problem) -> CheckItem {
+    let problem = match resolve_guest_path(rootfs, guest_path).and_then(|path| fs::read_to_string(path).ok()) {
+        None => Some(Problem::Missing),
+        Some(content) if !valid(&content) => Some(problem),
+        Some(_) => None,
+    };
+    CheckItem::new(kind, guest_path, problem)
+}
+
+pub(super) fn has_nameserver(content: &str) -> bool {
+    content.lines().any(|line| line.trim_start().starts_with("nameserver"))
+}
+
+pub(super) fn has_localhost(content: &str) -> bool {
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default())
+        .any(|line| line.split_whitespace().skip(1).any(|name| name == "localhost"))
+}
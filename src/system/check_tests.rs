@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+    use crate::config::ConfigManager;
+    use crate::distro::meta::META_FILE;
+    use crate::distro::SystemMeta;
+    use crate::system::check::*;
+    use crate::system::check_items::has_localhost;
+    use crate::system::launcher::{current_insos_path, render_start_script, DEFAULT_SHELL};
+    use crate::system::repair::{repair_item, set_mode, LOCALHOST_ENTRIES, NAMESERVERS};
+    use crate::utils::arch::detect_architecture;
+    use tempfile::TempDir;
+
+    fn problems(items: &[CheckItem]) -> Vec<(&str, Option<Problem>)> {
+        items.iter().map(|item| (item.subject.as_str(), item.problem.clone())).collect()
+    }
+
+    fn healthy_rootfs(root: &Path) {
+        for dir in ["usr/bin", "etc", "root", "tmp"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        set_mode(&root.join("tmp"), 0o1777).unwrap();
+        std::os::unix::fs::symlink("usr/bin", root.join("bin")).unwrap();
+        for binary in ["usr/bin/dash", "usr/bin/bash"] {
+            fs::write(root.join(binary), "").unwrap();
+            set_mode(&root.join(binary), 0o755).unwrap();
+        }
+        std::os::unix::fs::symlink("dash", root.join("usr/bin/sh")).unwrap();
+        fs::write(root.join("etc/os-release"), "ID=debian\n").unwrap();
+        fs::write(root.join("etc/resolv.conf"), NAMESERVERS).unwrap();
+        fs::write(root.join("etc/hosts"), LOCALHOST_ENTRIES).unwrap();
+
+        let meta = SystemMeta::new("debian1".to_string(), "debian".to_string(), detect_architecture().unwrap());
+        fs::write(root.join(META_FILE), meta.to_toml()).unwrap();
+        fs::write(root.join("start.sh"), render_start_script("debian1", &current_insos_path())).unwrap();
+        set_mode(&root.join("start.sh"), 0o755).unwrap();
+    }
+
+    #[test]
+    fn test_check_rootfs_healthy() {
+        let temp_dir = TempDir::new().unwrap();
+        healthy_rootfs(temp_dir.path());
+
+        let items = check_rootfs(temp_dir.path(), "debian1", DEFAULT_SHELL);
+        assert!(items.iter().all(|item| item.problem.is_none()), "{:?}", problems(&items));
+        assert!(items.iter().any(|item| item.subject == "/usr/bin/bash" || item.subject == "/bin/bash"));
+    }
+
+    #[test]
+    fn test_check_rootfs_reports_damage() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        healthy_rootfs(root);
+
+        set_mode(&root.join("tmp"), 0o755).unwrap();
+        set_mode(&root.join("usr/bin/dash"), 0o644).unwrap();
+        fs::remove_file(root.join("etc/resolv.conf")).unwrap();
+        std::os::unix::fs::symlink("/run/systemd/resolve/stub-resolv.conf", root.join("etc/resolv.conf")).unwrap();
+        fs::write(root.join(META_FILE), "schema_version = 1\n").unwrap();
+        fs::write(root.join("start.sh"), "#!/bin/sh\n").unwrap();
+
+        let items = check_rootfs(root, "debian1", "/bin/zsh --login");
+        let found = problems(&items);
+        assert!(matches!(found[0], (META_FILE, Some(Problem::Invalid(_)))));
+        assert!(found.contains(&("start.sh", Some(Problem::Outdated))));
+        assert!(found.contains(&("/bin/sh", Some(Problem::NotExecutable))));
+        assert!(found.contains(&("/bin/zsh", Some(Problem::Missing))));
+        assert!(found.contains(&("/tmp", Some(Problem::Mode { expected: 0o1777, actual: 0o755 }))));
+        assert!(found.contains(&("/etc/resolv.conf", Some(Problem::Missing))));
+
+        let zsh = items.iter().find(|item| item.subject == "/bin/zsh").unwrap();
+        assert!(!zsh.is_repairable());
+        assert!(items.iter().find(|item| item.subject == "/tmp").unwrap().is_repairable());
+    }
+
+    #[test]
+    fn test_repair_network_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        healthy_rootfs(root);
+        fs::remove_file(root.join("etc/resolv.conf")).unwrap();
+        std::os::unix::fs::symlink("/run/systemd/resolve/stub-resolv.conf", root.join("etc/resolv.conf")).unwrap();
+        fs::write(root.join("etc/hosts"), "10.0.0.2 nas").unwrap();
+        let config_manager = ConfigManager::with_dir(root);
+
+        for item in check_rootfs(root, "debian1", DEFAULT_SHELL).iter().filter(|item| item.is_repairable()) {
+            repair_item(root, "debian1", item, &config_manager).unwrap();
+        }
+
+        assert_eq!(fs::read_to_string(root.join("etc/resolv.conf")).unwrap(), NAMESERVERS);
+        assert_eq!(fs::read_to_string(root.join("etc/hosts")).unwrap(), format!("10.0.0.2 nas\n{}", LOCALHOST_ENTRIES));
+        assert!(check_rootfs(root, "debian1", DEFAULT_SHELL).iter().all(|item| item.problem.is_none()));
+    }
+
+    #[test]
+    fn test_has_localhost() {
+        assert!(has_localhost("127.0.0.1\tlocalhost localhost.localdomain\n"));
+        assert!(!has_localhost("# 127.0.0.1 localhost\n10.0.0.2 nas\n"));
+    }
+}
@@ -0,0 +1,171 @@
+use std::path::Path;
+use crate::config::ConfigManager;
+use crate::distro::{package_manager_for, PackageManager};
+use crate::i18n::Translator;
+use crate::system::audio::PULSE_SERVER;
+use crate::system::backend::LaunchSpec;
+use crate::system::launcher::{enter_system, run_guest_command, set_env_var, system_backend_and_spec};
+use crate::utils::fs::{load_system_meta, save_system_meta};
+
+pub const DEFAULT_DISPLAY: &str = ":0";
+pub const GUEST_X11_DIR: &str = "/tmp/.X11-unix";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DesktopEnvironment {
+    #[default]
+    Xfce4,
+    Lxqt,
+    Mate,
+}
+
+impl DesktopEnvironment {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "xfce" | "xfce4" => Some(DesktopEnvironment::Xfce4),
+            "lxqt" => Some(DesktopEnvironment::Lxqt),
+            "mate" => Some(DesktopEnvironment::Mate),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DesktopEnvironment::Xfce4 => "xfce4",
+            DesktopEnvironment::Lxqt => "lxqt",
+            DesktopEnvironment::Mate => "mate",
+        }
+    }
+
+    pub fn packages(&self, manager: PackageManager) -> Vec<&'static str> {
+        use PackageManager::*;
+
+        let mut packages = match (self, manager) {
+            (DesktopEnvironment::Xfce4, Apt | Pacman | Apk | Xbps) => vec!["xfce4", "xfce4-terminal"],
+            (DesktopEnvironment::Xfce4, Dnf | Yum | Zypper) => vec!["xfce4-session", "xfwm4", "xfce4-panel", "xfdesktop", "xfce4-terminal"],
+            (DesktopEnvironment::Lxqt, Dnf | Yum) => vec!["lxqt-session", "lxqt-panel", "openbox", "qterminal"],
+            (DesktopEnvironment::Lxqt, _) => vec!["lxqt", "openbox"],
+            (DesktopEnvironment::Mate, Apt) => vec!["mate-desktop-environment-core", "mate-terminal"],
+            (DesktopEnvironment::Mate, Dnf | Yum | Zypper) => vec!["mate-session-manager", "marco", "mate-panel", "mate-terminal"],
+            (DesktopEnvironment::Mate, _) => vec!["mate", "mate-terminal"],
+        };
+
+        packages.push(match manager {
+            Pacman => "dbus",
+            Zypper => "dbus-1-x11",
+            _ => "dbus-x11",
+        });
+        packages
+    }
+
+    pub fn session_command(&self) -> Vec<String> {
+        let session = match self {
+            DesktopEnvironment::Xfce4 => "startxfce4",
+            DesktopEnvironment::Lxqt => "startlxqt",
+            DesktopEnvironment::Mate => "mate-session",
+        };
+        vec!["dbus-launch".to_string(), "--exit-with-session".to_string(), session.to_string()]
+    }
+}
+
+/// Termux:X11 and Termux's X servers create their sockets under `$TMPDIR`.
+pub fn host_x11_dir() -> String {
+    let tmp = std::env::var("TMPDIR").unwrap_or_else(|_| "/tmp".to_string());
+    format!("{}/.X11-unix", tmp.trim_end_matches('/'))
+}
+
+/// Adds the X11 socket bind and the display/audio variables, keeping values
+/// already set through `env.*` in the config.
+pub fn apply_desktop_spec(spec: &mut LaunchSpec, x11_dir: &str, display: &str) {
+    spec.binds.push((x11_dir.to_string(), GUEST_X11_DIR.to_string()));
+
+    for (name, default) in [("DISPLAY", display), ("PULSE_SERVER", PULSE_SERVER)] {
+        if !spec.env.iter().any(|(key, _)| key == name) {
+            set_env_var(&mut spec.env, name, default);
+        }
+    }
+}
+
+fn install_desktop(system_id: &str, desktop: DesktopEnvironment, rootfs: &Path, translator: &Translator) -> Result<(), Box<dyn std::error::Error>> {
+    let distro = load_system_meta(system_id)?.distro;
+    let manager = package_manager_for(rootfs, &distro)
+        .ok_or_else(|| translator.t_fmt("package_manager_not_found", &[system_id]))?;
+
+    println!("{}", translator.t_fmt("installing_desktop", &[desktop.as_str(), manager.as_str()]));
+    let (backend, spec) = system_backend_and_spec(system_id, translator)?;
+    run_guest_command(backend.as_ref(), &spec, &manager.install_command(&desktop.packages(manager)))?;
+
+    let mut meta = load_system_meta(system_id)?;
+    meta.desktop = Some(desktop.as_str().to_string());
+    save_system_meta(system_id, &meta)
+}
+
+/// Installs the desktop on first use, then runs its session against the
+/// host's X server. Without `requested`, the DE stored in the metadata is used.
+pub fn start_desktop(system_id: &str, requested: Option<DesktopEnvironment>, translator: &Translator) -> Result<i32, Box<dyn std::error::Error>> {
+    let (_, spec) = system_backend_and_spec(system_id, translator)?;
+    let installed = load_system_meta(system_id)?
+        .desktop
+        .and_then(|name| DesktopEnvironment::from_str(&name));
+    let desktop = requested.or(installed).unwrap_or_default();
+
+    if installed != Some(desktop) {
+        install_desktop(system_id, desktop, &spec.rootfs, translator)?;
+    }
+
+    let x11_dir = host_x11_dir();
+    if !Path::new(&x11_dir).exists() {
+        println!("{}", translator.t_fmt("x11_socket_missing", &[&x11_dir]));
+    }
+
+    let display = ConfigManager::new()?
+        .get_system_setting(system_id, "display")?
+        .or_else(|| std::env::var("DISPLAY").ok())
+        .unwrap_or_else(|| DEFAULT_DISPLAY.to_string());
+
+    enter_system(system_id, desktop.session_command(), true, translator, |spec| {
+        apply_desktop_spec(spec, &x11_dir, &display)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_desktop_from_str() {
+        assert_eq!(DesktopEnvironment::from_str("XFCE"), Some(DesktopEnvironment::Xfce4));
+        assert_eq!(DesktopEnvironment::from_str("lxqt"), Some(DesktopEnvironment::Lxqt));
+        assert_eq!(DesktopEnvironment::from_str("gnome"), None);
+        assert_eq!(DesktopEnvironment::default().as_str(), "xfce4");
+    }
+
+    #[test]
+    fn test_desktop_packages_include_dbus() {
+        assert_eq!(
+            DesktopEnvironment::Xfce4.packages(PackageManager::Apt),
+            vec!["xfce4", "xfce4-terminal", "dbus-x11"]
+        );
+        assert!(DesktopEnvironment::Mate.packages(PackageManager::Pacman).contains(&"dbus"));
+    }
+
+    #[test]
+    fn test_apply_desktop_spec_keeps_configured_env() {
+        let mut spec = LaunchSpec {
+            rootfs: PathBuf::from("/data/termos/debian1"),
+            env: vec![("PULSE_SERVER".to_string(), "tcp:10.0.0.2".to_string())],
+            workdir: "/root".to_string(),
+            binds: Vec::new(),
+            argv: Vec::new(),
+            emulator: None,
+        };
+
+        apply_desktop_spec(&mut spec, "/data/tmp/.X11-unix", ":1");
+
+        assert_eq!(spec.binds, vec![("/data/tmp/.X11-unix".to_string(), GUEST_X11_DIR.to_string())]);
+        assert_eq!(spec.env, vec![
+            ("PULSE_SERVER".to_string(), "tcp:10.0.0.2".to_string()),
+            ("DISPLAY".to_string(), ":1".to_string()),
+        ]);
+    }
+}
@@ -0,0 +1,135 @@
+use std::collections::HashSet;
+use std::fs;
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+use std::process::Command;
+use crate::distro::meta::{current_timestamp, DiskUsage};
+use crate::distro::SystemMeta;
+use crate::utils::fs::{get_system_dir, save_system_meta};
+
+/// Walks `root` like `du -x`: symlinks are not followed, other filesystems
+/// (e.g. a chroot's `/proc`) are skipped and hardlinked files count once.
+/// Entries that cannot be read are ignored.
+pub fn scan_disk_usage(root: &Path) -> Result<DiskUsage, Box<dyn std::error::Error>> {
+    let root_meta = fs::symlink_metadata(root)?;
+    let device = root_meta.dev();
+    let mut seen = HashSet::new();
+    let mut apparent_bytes = root_meta.len();
+    let mut disk_bytes = root_meta.blocks() * 512;
+    let mut pending = vec![root.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+
+        for entry in entries.flatten() {
+            let Ok(meta) = entry.metadata() else {
+                continue;
+            };
+            if meta.dev() != device {
+                continue;
+            }
+            if meta.is_dir() {
+                pending.push(entry.path());
+            } else if meta.nlink() > 1 && !seen.insert(meta.ino()) {
+                continue;
+            }
+
+            apparent_bytes += meta.len();
+            disk_bytes += meta.blocks() * 512;
+        }
+    }
+
+    Ok(DiskUsage {
+        apparent_bytes,
+        disk_bytes,
+        scanned_at: current_timestamp(),
+    })
+}
+
+/// Fills in the cached usage of each system, rescanning all of them when
+/// `refresh` is set and only those never scanned otherwise.
+pub fn update_disk_usage(metas: &mut [(String, SystemMeta)], refresh: bool) -> Result<(), Box<dyn std::error::Error>> {
+    for (system_id, meta) in metas.iter_mut() {
+        if meta.disk_usage.is_some() && !refresh {
+            continue;
+        }
+        meta.disk_usage = Some(scan_disk_usage(&get_system_dir(system_id)?)?);
+        save_system_meta(system_id, meta)?;
+    }
+    Ok(())
+}
+
+/// Sum of `(apparent, on-disk)` bytes over the systems that have been scanned.
+pub fn total_disk_usage(metas: &[(String, SystemMeta)]) -> (u64, u64) {
+    metas
+        .iter()
+        .filter_map(|(_, meta)| meta.disk_usage.as_ref())
+        .fold((0, 0), |(apparent, disk), usage| (apparent + usage.apparent_bytes, disk + usage.disk_bytes))
+}
+
+/// Bytes available to unprivileged users on the filesystem holding `path`,
+/// as reported by `df`.
+pub fn free_space(path: &Path) -> Result<u64, Box<dyn std::error::Error>> {
+    let output = Command::new("df").arg("-Pk").arg(path).output()?;
+    if !output.status.success() {
+        return Err(format!("df 执行失败: {}", String::from_utf8_lossy(&output.stderr).trim()).into());
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    parse_df_available(&stdout).ok_or_else(|| format!("无法解析 df 输出: {}", stdout).into())
+}
+
+/// Available column of `df -P -k` output, in bytes.
+fn parse_df_available(output: &str) -> Option<u64> {
+    let kib: u64 = output.lines().nth(1)?.split_whitespace().nth(3)?.parse().ok()?;
+    Some(kib * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::arch::Architecture;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_scan_disk_usage_counts_hardlinks_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let empty = scan_disk_usage(temp_dir.path()).unwrap();
+
+        fs::create_dir(temp_dir.path().join("usr")).unwrap();
+        fs::write(temp_dir.path().join("usr/busybox"), vec![0u8; 10_000]).unwrap();
+        let single = scan_disk_usage(temp_dir.path()).unwrap();
+        assert!(single.apparent_bytes >= empty.apparent_bytes + 10_000);
+        assert!(single.disk_bytes >= empty.disk_bytes);
+
+        fs::hard_link(temp_dir.path().join("usr/busybox"), temp_dir.path().join("usr/sh")).unwrap();
+        let linked = scan_disk_usage(temp_dir.path()).unwrap();
+        assert!(linked.apparent_bytes < single.apparent_bytes + 10_000);
+        assert!(linked.disk_bytes < single.disk_bytes + 10_000);
+    }
+
+    #[test]
+    fn test_total_disk_usage() {
+        let mut scanned = SystemMeta::new("debian1".to_string(), "debian".to_string(), Architecture::Aarch64);
+        scanned.disk_usage = Some(DiskUsage {
+            apparent_bytes: 100,
+            disk_bytes: 150,
+            scanned_at: current_timestamp(),
+        });
+        let unscanned = SystemMeta::new("alpine1".to_string(), "alpine".to_string(), Architecture::Aarch64);
+
+        let metas = vec![("debian1".to_string(), scanned.clone()), ("debian2".to_string(), scanned), ("alpine1".to_string(), unscanned)];
+        assert_eq!(total_disk_usage(&metas), (200, 300));
+    }
+
+    #[test]
+    fn test_free_space() {
+        let output = "Filesystem     1024-blocks      Used Available Capacity Mounted on\n/dev/block/dm-5   115276268 101234560  14041708      88% /data\n";
+        assert_eq!(parse_df_available(output), Some(14041708 * 1024));
+        assert_eq!(parse_df_available("Filesystem 1024-blocks Used Available Capacity Mounted on\n"), None);
+
+        let temp_dir = TempDir::new().unwrap();
+        assert!(free_space(temp_dir.path()).unwrap() > 0);
+    }
+}
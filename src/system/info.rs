@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::config::ConfigManager;
+use crate::distro::{package_manager_for, PackageManager, SystemMeta};
+use crate::i18n::Translator;
+use crate::system::disk_usage::scan_disk_usage;
+use crate::system::launcher::build_launch_spec;
+use crate::system::services::running_services;
+use crate::system::session::list_sessions;
+use crate::utils::fs::{get_system_dir, load_system_meta, resolve_guest_path, save_system_meta};
+
+/// Metadata of a system plus what can only be read from the rootfs or the
+/// current configuration.
+#[derive(Debug, Clone)]
+pub struct SystemInfo {
+    pub id: String,
+    pub meta: SystemMeta,
+    pub path: PathBuf,
+    pub launcher: PathBuf,
+    /// `PRETTY_NAME` and `VERSION_ID` from the guest's os-release.
+    pub pretty_name: Option<String>,
+    pub version_id: Option<String>,
+    pub package_manager: Option<PackageManager>,
+    pub package_count: Option<usize>,
+    pub binds: Vec<(String, String)>,
+    pub env: Vec<(String, String)>,
+    pub sessions: usize,
+    pub services: Vec<String>,
+}
+
+pub fn parse_os_release(content: &str) -> HashMap<String, String> {
+    content
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| {
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+                .unwrap_or(value);
+            (key.trim().to_string(), value.to_string())
+        })
+        .collect()
+}
+
+/// Reads the guest's os-release, which is often a symlink into `/usr/lib`.
+pub fn read_os_release(rootfs: &Path) -> Option<HashMap<String, String>> {
+    let path = resolve_guest_path(rootfs, "/etc/os-release")
+        .or_else(|| resolve_guest_path(rootfs, "/usr/lib/os-release"))?;
+    fs::read_to_string(path).ok().map(|content| parse_os_release(&content))
+}
+
+/// Collects the details shown by `insOs info`, rescanning the disk usage
+/// and storing it in the metadata.
+pub fn collect_system_info(system_id: &str, translator: &Translator) -> Result<SystemInfo, Box<dyn std::error::Error>> {
+    let path = get_system_dir(system_id)?;
+    if !path.exists() {
+        return Err(translator.t_fmt("system_not_exist", &[system_id]).into());
+    }
+
+    let mut meta = load_system_meta(system_id)?;
+    meta.disk_usage = Some(scan_disk_usage(&path)?);
+    save_system_meta(system_id, &meta)?;
+
+    let os_release = read_os_release(&path).unwrap_or_default();
+    let package_manager = package_manager_for(&path, &meta.distro);
+    let spec = build_launch_spec(system_id, &path, Vec::new(), &ConfigManager::new()?)?;
+
+    Ok(SystemInfo {
+        id: system_id.to_string(),
+        launcher: path.join("start.sh"),
+        pretty_name: os_release.get("PRETTY_NAME").cloned(),
+        version_id: os_release.get("VERSION_ID").cloned(),
+        package_count: package_manager.and_then(|manager| manager.installed_count(&path)),
+        package_manager,
+        binds: spec.binds,
+        env: spec.env,
+        sessions: list_sessions(&path)?.len(),
+        services: running_services(&path).into_iter().map(|(name, _)| name).collect(),
+        meta,
+        path,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_os_release() {
+        let content = "# comment\nNAME=\"Debian GNU/Linux\"\nVERSION_ID='12'\nID=debian\nPRETTY_NAME=\"Debian GNU/Linux 12 (bookworm)\"\n";
+        let fields = parse_os_release(content);
+        assert_eq!(fields.get("PRETTY_NAME").map(String::as_str), Some("Debian GNU/Linux 12 (bookworm)"));
+        assert_eq!(fields.get("VERSION_ID").map(String::as_str), Some("12"));
+        assert_eq!(fields.get("ID").map(String::as_str), Some("debian"));
+    }
+
+    #[test]
+    fn test_read_os_release_resolves_absolute_symlink() {
+        let temp_dir = TempDir::new().unwrap();
+        let rootfs = temp_dir.path();
+        fs::create_dir_all(rootfs.join("etc")).unwrap();
+        fs::create_dir_all(rootfs.join("usr/lib")).unwrap();
+        fs::write(rootfs.join("usr/lib/os-release"), "ID=alpine\nVERSION_ID=3.20.0\n").unwrap();
+        std::os::unix::fs::symlink("/usr/lib/os-release", rootfs.join("etc/os-release")).unwrap();
+
+        let fields = read_os_release(rootfs).unwrap();
+        assert_eq!(fields.get("ID").map(String::as_str), Some("alpine"));
+        assert_eq!(fields.get("VERSION_ID").map(String::as_str), Some("3.20.0"));
+    }
+}
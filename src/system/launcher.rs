@@ -0,0 +1,347 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use crate::config::ConfigManager;
+use crate::i18n::Translator;
+use crate::system::audio::{ensure_pulse_tcp, is_audio_enabled, AudioBridge, PULSE_SERVER};
+use crate::system::backend::{backend_for, launch, teardown, ContainerBackend, LaunchSpec};
+use crate::system::services::{has_running_services, start_configured_services, stop_all_services};
+use crate::system::session::is_system_running;
+use crate::system::volumes::resolve_volume_binds;
+use crate::utils::arch::{find_qemu_binary, Architecture};
+use crate::utils::cmd::shell_quote;
+use crate::distro::meta::current_timestamp;
+use crate::utils::fs::{get_system_dir, load_system_meta, save_system_meta};
+
+pub const GUEST_PATH: &str = "/usr/local/sbin:/usr/local/bin:/usr/sbin:/usr/bin:/sbin:/bin";
+pub const GUEST_HOME: &str = "/root";
+pub const DEFAULT_TERM: &str = "xterm-256color";
+pub const DEFAULT_LOCALE: &str = "C.UTF-8";
+pub const DEFAULT_SHELL: &str = "/bin/bash --login";
+
+/// Environment handed to the guest through `env -i`, so nothing from Termux
+/// (`PREFIX`, `LD_PRELOAD`, its `PATH`) leaks into the session.
+pub fn build_guest_env(
+    system_id: &str,
+    rootfs: &Path,
+    config_manager: &ConfigManager,
+) -> Result<Vec<(String, String)>, Box<dyn std::error::Error>> {
+    let term = std::env::var("TERM").unwrap_or_else(|_| DEFAULT_TERM.to_string());
+    let locale = read_guest_locale(rootfs).unwrap_or_else(|| DEFAULT_LOCALE.to_string());
+    
+    let mut env = vec![
+        ("HOME".to_string(), GUEST_HOME.to_string()),
+        ("PATH".to_string(), GUEST_PATH.to_string()),
+        ("TERM".to_string(), term),
+        ("LANG".to_string(), locale),
+    ];
+
+    if let Some(timezone) = config_manager.get_timezone_for_system(system_id)? {
+        env.push(("TZ".to_string(), timezone));
+    }
+
+    for (name, value) in config_manager.get_env_for_system(system_id)? {
+        set_env_var(&mut env, &name, &value);
+    }
+
+    Ok(env)
+}
+
+pub fn set_env_var(env: &mut Vec<(String, String)>, name: &str, value: &str) {
+    match env.iter_mut().find(|(key, _)| key == name) {
+        Some(entry) => entry.1 = value.to_string(),
+        None => env.push((name.to_string(), value.to_string())),
+    }
+}
+
+fn read_guest_locale(rootfs: &Path) -> Option<String> {
+    let content = fs::read_to_string(rootfs.join("etc/locale.conf")).ok()?;
+    content
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("LANG="))
+        .map(|value| value.trim_matches('"').to_string())
+        .filter(|value| !value.is_empty())
+}
+
+pub fn build_launch_spec(
+    system_id: &str,
+    rootfs: &Path,
+    argv: Vec<String>,
+    config_manager: &ConfigManager,
+) -> Result<LaunchSpec, Box<dyn std::error::Error>> {
+    Ok(LaunchSpec {
+        rootfs: rootfs.to_path_buf(),
+        env: build_guest_env(system_id, rootfs, config_manager)?,
+        workdir: GUEST_HOME.to_string(),
+        binds: resolve_volume_binds(config_manager, system_id)?,
+        argv,
+        emulator: None,
+    })
+}
+
+pub fn resolve_emulator(emulated_arch: Option<Architecture>) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    match emulated_arch {
+        Some(arch) => find_qemu_binary(&arch)
+            .map(Some)
+            .ok_or_else(|| format!("未找到 {}，请先安装 qemu-user", arch.qemu_binary()).into()),
+        None => Ok(None),
+    }
+}
+
+pub fn login_system(system_id: &str, translator: &Translator) -> Result<i32, Box<dyn std::error::Error>> {
+    let config_manager = ConfigManager::new()?;
+    let shell_cmd = config_manager.get_shell_command()?.unwrap_or_else(|| DEFAULT_SHELL.to_string());
+    let argv = shell_cmd.split_whitespace().map(String::from).collect();
+    enter_system(system_id, argv, true, translator, |_| {})
+}
+
+pub fn run_in_system(system_id: &str, argv: Vec<String>, translator: &Translator) -> Result<i32, Box<dyn std::error::Error>> {
+    enter_system(system_id, argv, false, translator, |_| {})
+}
+
+/// Backend and launch spec (with an empty `argv`) for an installed system.
+pub fn system_backend_and_spec(system_id: &str, translator: &Translator) -> Result<(Box<dyn ContainerBackend>, LaunchSpec), Box<dyn std::error::Error>> {
+    let system_dir = get_system_dir(system_id)?;
+    if !system_dir.exists() {
+        return Err(translator.t_fmt("system_not_exist", &[system_id]).into());
+    }
+    
+    let meta = load_system_meta(system_id)?;
+    let config_manager = ConfigManager::new()?;
+    let mut spec = build_launch_spec(system_id, &system_dir, Vec::new(), &config_manager)?;
+    spec.emulator = resolve_emulator(meta.emulated_arch())?;
+    Ok((backend_for(meta.backend), spec))
+}
+
+/// Runs `argv` as a session of the system. Configured services are started
+/// on login and stopped once the last session has exited; `customize` may
+/// add binds or environment before the session starts.
+pub fn enter_system(
+    system_id: &str,
+    argv: Vec<String>,
+    with_services: bool,
+    translator: &Translator,
+    customize: impl FnOnce(&mut LaunchSpec),
+) -> Result<i32, Box<dyn std::error::Error>> {
+    let (backend, mut spec) = system_backend_and_spec(system_id, translator)?;
+    let config_manager = ConfigManager::new()?;
+    spec.argv = argv;
+    customize(&mut spec);
+    
+    if is_audio_enabled(&config_manager, system_id)? {
+        match ensure_pulse_tcp() {
+            Ok(AudioBridge::Ready) if !spec.env.iter().any(|(name, _)| name == "PULSE_SERVER") => {
+                set_env_var(&mut spec.env, "PULSE_SERVER", PULSE_SERVER);
+            }
+            Ok(AudioBridge::Ready) => {}
+            Ok(AudioBridge::Unavailable) => eprintln!("{}", translator.t("pulseaudio_not_found")),
+            Err(e) => eprintln!("{}", translator.t_fmt("audio_bridge_failed", &[&e.to_string()])),
+        }
+    }
+    
+    backend
+        .check_available()
+        .map_err(|reason| translator.t_fmt("backend_unavailable", &[backend.kind().as_str(), &reason]))?;
+    if with_services {
+        start_configured_services(system_id, &config_manager, backend.as_ref(), &spec, translator)?;
+        let mut meta = load_system_meta(system_id)?;
+        meta.last_login = Some(current_timestamp());
+        save_system_meta(system_id, &meta)?;
+    }
+    
+    let result = launch(backend.as_ref(), &spec);
+    if !is_system_running(&spec.rootfs) && has_running_services(&spec.rootfs) {
+        stop_all_services(&spec.rootfs);
+        teardown(backend.as_ref(), &spec);
+    }
+    result
+}
+
+/// The launcher script only delegates to the native `insOs login` path, so
+/// every backend is entered through the same code.
+pub fn render_start_script(system_id: &str, insos_path: &str) -> String {
+    format!(
+        r#"#!/bin/bash
+INSOS={insos}
+[ -x "$INSOS" ] || INSOS=insOs
+exec "$INSOS" login {id} "$@"
+"#,
+        insos = shell_quote(insos_path),
+        id = shell_quote(system_id)
+    )
+}
+
+pub fn current_insos_path() -> String {
+    std::env::current_exe()
+        .map(|path| path.to_string_lossy().to_string())
+        .unwrap_or_else(|_| "insOs".to_string())
+}
+
+pub fn write_start_script(system_dir: &Path, system_id: &str) -> Result<(), Box<dyn std::error::Error>> {
+    use std::os::unix::fs::PermissionsExt;
+    
+    let start_script_path = system_dir.join("start.sh");
+    fs::write(&start_script_path, render_start_script(system_id, &current_insos_path()))?;
+
+    let mut perms = fs::metadata(&start_script_path)?.permissions();
+    perms.set_mode(0o755);
+    fs::set_permissions(&start_script_path, perms)?;
+
+    Ok(())
+}
+
+/// Android keeps the zone in a system property; elsewhere fall back to `TZ`.
+pub fn detect_host_timezone() -> Option<String> {
+    let from_getprop = Command::new("getprop")
+        .arg("persist.sys.timezone")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|tz| !tz.is_empty());
+
+    from_getprop.or_else(|| std::env::var("TZ").ok().filter(|tz| !tz.is_empty()))
+}
+
+/// Writes timezone and locale files into the rootfs. Returns true when the
+/// guest has `locale-gen` and it still needs to be run inside the system.
+pub fn configure_guest_locale(rootfs: &Path, locale: &str, timezone: Option<&str>) -> Result<bool, Box<dyn std::error::Error>> {
+    let etc_dir = rootfs.join("etc");
+    fs::create_dir_all(&etc_dir)?;
+
+    if let Some(tz) = timezone {
+        fs::write(etc_dir.join("timezone"), format!("{}\n", tz))?;
+
+        if rootfs.join("usr/share/zoneinfo").join(tz).exists() {
+            let localtime = etc_dir.join("localtime");
+            if localtime.symlink_metadata().is_ok() {
+                fs::remove_file(&localtime)?;
+            }
+            std::os::unix::fs::symlink(format!("/usr/share/zoneinfo/{}", tz), &localtime)?;
+        }
+    }
+
+    fs::write(etc_dir.join("locale.conf"), format!("LANG={}\n", locale))?;
+    if etc_dir.join("default").is_dir() {
+        fs::write(etc_dir.join("default/locale"), format!("LANG={}\n", locale))?;
+    }
+
+    let locale_gen = etc_dir.join("locale.gen");
+    if !locale_gen.exists() {
+        return Ok(false);
+    }
+
+    let content = fs::read_to_string(&locale_gen)?;
+    fs::write(&locale_gen, enable_locale_entry(&content, locale))?;
+
+    let has_locale_gen = ["usr/sbin/locale-gen", "usr/bin/locale-gen", "sbin/locale-gen"]
+        .iter()
+        .any(|path| rootfs.join(path).exists());
+
+    Ok(has_locale_gen)
+}
+
+fn enable_locale_entry(content: &str, locale: &str) -> String {
+    let charset = locale.rsplit('.').next().unwrap_or("UTF-8");
+    let entry = format!("{} {}", locale, charset);
+    let mut found = false;
+
+    let mut lines: Vec<String> = content
+        .lines()
+        .map(|line| {
+            let uncommented = line.trim_start_matches('#').trim();
+            if uncommented == entry {
+                found = true;
+                entry.clone()
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+
+    if !found {
+        lines.push(entry);
+    }
+
+    let mut result = lines.join("\n");
+    result.push('\n');
+    result
+}
+
+pub fn run_guest_command(backend: &dyn ContainerBackend, spec: &LaunchSpec, command: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let mut spec = spec.clone();
+    spec.argv = vec!["/bin/sh".to_string(), "-c".to_string(), command.to_string()];
+    
+    let code = launch(backend, &spec)?;
+    if code != 0 {
+        return Err(format!("命令执行失败: {} (exit {})", command, code).into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_render_start_script_delegates_to_login() {
+        let script = render_start_script("debian1", "/usr/bin/insOs");
+
+        assert!(script.contains("INSOS='/usr/bin/insOs'"));
+        assert!(script.contains("exec \"$INSOS\" login 'debian1' \"$@\""));
+    }
+
+    #[test]
+    fn test_read_guest_locale() {
+        let temp_dir = TempDir::new().unwrap();
+        assert_eq!(read_guest_locale(temp_dir.path()), None);
+        
+        fs::create_dir_all(temp_dir.path().join("etc")).unwrap();
+        fs::write(temp_dir.path().join("etc/locale.conf"), "LANG=\"zh_CN.UTF-8\"\n").unwrap();
+        assert_eq!(read_guest_locale(temp_dir.path()), Some("zh_CN.UTF-8".to_string()));
+    }
+
+    #[test]
+    fn test_set_env_var_overrides() {
+        let mut env = vec![("LANG".to_string(), "C".to_string())];
+        set_env_var(&mut env, "LANG", "zh_CN.UTF-8");
+        set_env_var(&mut env, "EDITOR", "vim");
+
+        assert_eq!(env, vec![
+            ("LANG".to_string(), "zh_CN.UTF-8".to_string()),
+            ("EDITOR".to_string(), "vim".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_configure_guest_locale() {
+        let temp_dir = TempDir::new().unwrap();
+        let rootfs = temp_dir.path();
+        fs::create_dir_all(rootfs.join("etc/default")).unwrap();
+        fs::create_dir_all(rootfs.join("usr/share/zoneinfo/Asia")).unwrap();
+        fs::write(rootfs.join("usr/share/zoneinfo/Asia/Shanghai"), "").unwrap();
+        fs::write(rootfs.join("etc/locale.gen"), "# en_US.UTF-8 UTF-8\n# zh_CN.UTF-8 UTF-8\n").unwrap();
+
+        let needs_gen = configure_guest_locale(rootfs, "zh_CN.UTF-8", Some("Asia/Shanghai")).unwrap();
+
+        assert!(!needs_gen);
+        assert_eq!(fs::read_to_string(rootfs.join("etc/timezone")).unwrap(), "Asia/Shanghai\n");
+        assert_eq!(
+            fs::read_link(rootfs.join("etc/localtime")).unwrap(),
+            Path::new("/usr/share/zoneinfo/Asia/Shanghai")
+        );
+        assert_eq!(fs::read_to_string(rootfs.join("etc/default/locale")).unwrap(), "LANG=zh_CN.UTF-8\n");
+        assert_eq!(
+            fs::read_to_string(rootfs.join("etc/locale.gen")).unwrap(),
+            "# en_US.UTF-8 UTF-8\nzh_CN.UTF-8 UTF-8\n"
+        );
+    }
+
+    #[test]
+    fn test_enable_locale_entry_appends_missing() {
+        let result = enable_locale_entry("# de_DE.UTF-8 UTF-8\n", "en_US.UTF-8");
+        assert_eq!(result, "# de_DE.UTF-8 UTF-8\nen_US.UTF-8 UTF-8\n");
+    }
+}
@@ -1,18 +1,86 @@
 use std::fs;
-use std::path::PathBuf;
-use crate::utils::fs::get_home_dir;
-use crate::utils::cmd::run_command;
+use std::os::unix::ffi::OsStringExt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use crate::system::backend::teardown;
+use crate::system::launcher::system_backend_and_spec;
+use crate::system::services::{has_running_services, stop_all_services};
+use crate::system::launcher::write_start_script;
+use crate::system::session::{is_system_running, stop_sessions};
+use crate::system::shortcuts::remove_shortcuts;
+use crate::utils::fs::{get_home_dir, get_system_dir, load_system_meta, save_system_meta};
+use crate::utils::cmd::{run_command, shell_quote};
 use crate::i18n::Translator;
 
+const STOP_GRACE_PERIOD: Duration = Duration::from_secs(5);
+
+/// Mount points at or below `dir`, from `/proc/self/mountinfo`.
+pub fn mounts_under(dir: &Path) -> Vec<PathBuf> {
+    let mountinfo = fs::read_to_string("/proc/self/mountinfo").unwrap_or_default();
+    // mountinfo lists resolved paths, while $HOME may go through a symlink.
+    let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    parse_mount_points(&mountinfo)
+        .into_iter()
+        .filter(|mount_point| mount_point.starts_with(&dir))
+        .collect()
+}
+
+/// Fifth field of each mountinfo line, with the octal escapes (`\040` for
+/// a space) decoded.
+fn parse_mount_points(mountinfo: &str) -> Vec<PathBuf> {
+    mountinfo
+        .lines()
+        .filter_map(|line| line.split(' ').nth(4))
+        .map(|field| {
+            let mut decoded = Vec::new();
+            let bytes = field.as_bytes();
+            let mut i = 0;
+            while i < bytes.len() {
+                let escape = bytes.get(i + 1..i + 4).and_then(|digits| u8::from_str_radix(std::str::from_utf8(digits).ok()?, 8).ok());
+                match (bytes[i], escape) {
+                    (b'\\', Some(byte)) => {
+                        decoded.push(byte);
+                        i += 4;
+                    }
+                    (byte, _) => {
+                        decoded.push(byte);
+                        i += 1;
+                    }
+                }
+            }
+            PathBuf::from(std::ffi::OsString::from_vec(decoded))
+        })
+        .collect()
+}
+
 pub fn uninstall_system_by_id(system_id: &str, translator: &Translator) -> Result<(), Box<dyn std::error::Error>> {
     println!("\n{}", translator.t_fmt("uninstalling_system", &[system_id]));
     
     let home = get_home_dir()?;
     let system_dir = home.join("termos").join(system_id);
     
+    if is_system_running(&system_dir) || has_running_services(&system_dir) {
+        return Err(translator.t_fmt("system_running_stop_first", &[system_id]).into());
+    }
+    
     if system_dir.exists() {
-        run_command(&format!("chmod 777 -R {}", system_dir.display()))?;
-        run_command(&format!("rm -rf {}", system_dir.display()))?;
+        // A bind mount left inside the rootfs would take host /dev, /sys or
+        // a shared volume down with it.
+        if let Ok((backend, spec)) = system_backend_and_spec(system_id, translator) {
+            teardown(backend.as_ref(), &spec);
+        }
+        let remaining = mounts_under(&system_dir);
+        if !remaining.is_empty() {
+            let list: Vec<String> = remaining.iter().map(|path| path.display().to_string()).collect();
+            return Err(translator.t_fmt("system_mounts_remaining", &[system_id, &list.join(", ")]).into());
+        }
+        
+        // Only directories need to be writable for rm; chmod has no
+        // --one-file-system, so find -xdev keeps it on this filesystem.
+        let dir = shell_quote(&system_dir.display().to_string());
+        run_command(&format!("find {} -xdev -type d ! -perm -u+rwx -exec chmod u+rwx {{}} +", dir))?;
+        run_command(&format!("rm -rf --one-file-system {}", dir))?;
+        remove_shortcuts(system_id)?;
         println!("\n{}", &translator.t("uninstall_complete"));
     } else {
         println!("{}", translator.t_fmt("system_not_exist", &[system_id]));
@@ -21,6 +89,52 @@ pub fn uninstall_system_by_id(system_id: &str, translator: &Translator) -> Resul
     Ok(())
 }
 
+/// Moves the system to a new ID, rewriting its metadata and `start.sh`.
+/// Shortcuts pointing at the old ID are removed.
+pub fn rename_system_by_id(old_id: &str, new_id: &str, translator: &Translator) -> Result<(), Box<dyn std::error::Error>> {
+    if new_id.is_empty() || new_id.starts_with('.') || new_id.contains('/') {
+        return Err(translator.t_fmt("invalid_system_id", &[new_id]).into());
+    }
+    
+    let old_dir = get_system_dir(old_id)?;
+    let new_dir = get_system_dir(new_id)?;
+    if !old_dir.exists() {
+        return Err(translator.t_fmt("system_not_exist", &[old_id]).into());
+    }
+    if new_dir.exists() {
+        return Err(translator.t_fmt("system_already_exists", &[new_id]).into());
+    }
+    if is_system_running(&old_dir) || has_running_services(&old_dir) {
+        return Err(translator.t_fmt("system_running_stop_first", &[old_id]).into());
+    }
+    
+    fs::rename(&old_dir, &new_dir)?;
+    let mut meta = load_system_meta(new_id)?;
+    meta.name = new_id.to_string();
+    save_system_meta(new_id, &meta)?;
+    write_start_script(&new_dir, new_id)?;
+    remove_shortcuts(old_id)?;
+    
+    Ok(())
+}
+
+/// Terminates every session and service of the system and releases the
+/// backend's mounts, returning how many sessions were running.
+pub fn stop_system(system_id: &str, translator: &Translator) -> Result<usize, Box<dyn std::error::Error>> {
+    let system_dir = get_system_dir(system_id)?;
+    if !system_dir.exists() {
+        return Err(translator.t_fmt("system_not_exist", &[system_id]).into());
+    }
+    
+    let stopped = stop_sessions(&system_dir, STOP_GRACE_PERIOD)?;
+    stop_all_services(&system_dir);
+    
+    let (backend, spec) = system_backend_and_spec(system_id, translator)?;
+    teardown(backend.as_ref(), &spec);
+    
+    Ok(stopped)
+}
+
 #[allow(dead_code)]
 pub struct SystemManager {
     base_dir: PathBuf,
@@ -69,3 +183,21 @@ impl SystemManager {
         self.get_system_dir(system_id).exists()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mount_points() {
+        let mountinfo = "22 1 259:2 / / rw,relatime shared:1 - ext4 /dev/root rw\n\
+            95 22 0:5 / /data/termos/debian1/dev rw,nosuid shared:2 - devtmpfs udev rw\n\
+            96 22 259:2 /share /data/termos/my\\040volume rw - ext4 /dev/root rw\n";
+        assert_eq!(parse_mount_points(mountinfo), vec![
+            PathBuf::from("/"),
+            PathBuf::from("/data/termos/debian1/dev"),
+            PathBuf::from("/data/termos/my volume"),
+        ]);
+        assert!(mounts_under(Path::new("/nonexistent/termos/debian1")).is_empty());
+    }
+}
@@ -0,0 +1,151 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::distro::PackageManager;
+use crate::system::session::STATE_DIR;
+use crate::utils::fs::resolve_guest_path;
+use crate::system::mirror_rewrite::{rewrite_apk, rewrite_deb822, rewrite_pacman, rewrite_repo_file, rewrite_sources_list, rewrite_xbps};
+
+/// Originals of rewritten files, stored under their guest path.
+const BACKUP_DIR: &str = "mirror-backup";
+/// Guest paths of files that did not exist before the mirror was applied.
+const CREATED_LIST: &str = "created.list";
+
+fn backup_dir(rootfs: &Path) -> PathBuf {
+    rootfs.join(STATE_DIR).join(BACKUP_DIR)
+}
+
+/// Files in guest directory `dir` whose name ends with `suffix`, sorted.
+fn guest_files(rootfs: &Path, dir: &str, suffix: &str) -> Vec<String> {
+    let Some(entries) = resolve_guest_path(rootfs, dir).and_then(|dir| fs::read_dir(dir).ok()) else {
+        return Vec::new();
+    };
+    let mut files: Vec<String> = entries
+        .flatten()
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .filter(|name| name.ends_with(suffix))
+        .map(|name| format!("{}/{}", dir, name))
+        .collect();
+    files.sort();
+    files
+}
+
+type Rewrite = fn(&str, &str) -> Option<String>;
+
+/// `(source, target, rewrite)` for each file the package manager reads its
+/// repositories from. The source differs from the target only for xbps,
+/// whose defaults in `/usr/share` are overridden by files of the same name in `/etc`.
+fn repository_files(rootfs: &Path, manager: PackageManager) -> Vec<(String, String, Rewrite)> {
+    let same = |files: Vec<String>, rewrite: Rewrite| -> Vec<(String, String, Rewrite)> {
+        files.into_iter().map(|file| (file.clone(), file, rewrite)).collect()
+    };
+    match manager {
+        PackageManager::Apt => {
+            let mut files = same(vec!["/etc/apt/sources.list".to_string()], rewrite_sources_list);
+            files.extend(same(guest_files(rootfs, "/etc/apt/sources.list.d", ".list"), rewrite_sources_list));
+            files.extend(same(guest_files(rootfs, "/etc/apt/sources.list.d", ".sources"), rewrite_deb822));
+            files
+        }
+        PackageManager::Pacman => same(vec!["/etc/pacman.d/mirrorlist".to_string()], rewrite_pacman),
+        PackageManager::Dnf | PackageManager::Yum => {
+            same(guest_files(rootfs, "/etc/yum.repos.d", ".repo"), |content, mirror| rewrite_repo_file(content, mirror, &[]))
+        }
+        PackageManager::Zypper => same(guest_files(rootfs, "/etc/zypp/repos.d", ".repo"), |content, mirror| {
+            rewrite_repo_file(content, mirror, &["download.opensuse.org"])
+        }),
+        PackageManager::Apk => same(vec!["/etc/apk/repositories".to_string()], rewrite_apk),
+        PackageManager::Xbps => {
+            let overrides = guest_files(rootfs, "/etc/xbps.d", ".conf");
+            let mut files = same(overrides.clone(), rewrite_xbps);
+            for default in guest_files(rootfs, "/usr/share/xbps.d", ".conf") {
+                let target = default.replacen("/usr/share/xbps.d/", "/etc/xbps.d/", 1);
+                if !overrides.contains(&target) {
+                    files.push((default, target, rewrite_xbps));
+                }
+            }
+            files
+        }
+    }
+}
+
+/// Points the guest's package manager at `mirror`. Every file is rewritten
+/// from its original, which is backed up the first time, so applying
+/// another mirror later does not stack changes. Returns the number of
+/// files written; 0 if no repository entry matched the mirror.
+pub fn apply_mirror(rootfs: &Path, manager: PackageManager, mirror: &str) -> Result<usize, Box<dyn std::error::Error>> {
+    let backups = backup_dir(rootfs);
+    let list = backups.join(CREATED_LIST);
+    let mut created = fs::read_to_string(&list).unwrap_or_default();
+    let mut written = 0;
+
+    for (source, target, rewrite) in repository_files(rootfs, manager) {
+        let relative = target.trim_start_matches('/');
+        let backup = backups.join(relative);
+        let target_path = resolve_guest_path(rootfs, &target);
+        let original = match resolve_guest_path(rootfs, &source) {
+            _ if backup.exists() => fs::read_to_string(&backup)?,
+            Some(path) => fs::read_to_string(path)?,
+            None => continue,
+        };
+        let Some(content) = rewrite(&original, mirror) else {
+            continue;
+        };
+
+        if !backup.exists() {
+            fs::create_dir_all(backup.parent().unwrap_or(&backups))?;
+            fs::write(&backup, &original)?;
+            if target_path.is_none() {
+                created.push_str(&format!("{}\n", target));
+                fs::write(&list, &created)?;
+            }
+        }
+        let path = target_path.unwrap_or_else(|| rootfs.join(relative));
+        fs::create_dir_all(path.parent().unwrap_or(rootfs))?;
+        fs::write(path, content)?;
+        written += 1;
+    }
+    Ok(written)
+}
+
+fn backed_up_files(dir: &Path, prefix: &str, files: &mut Vec<String>) -> Result<(), Box<dyn std::error::Error>> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = format!("{}/{}", prefix, entry.file_name().to_string_lossy());
+        if entry.file_type()?.is_dir() {
+            backed_up_files(&entry.path(), &name, files)?;
+        } else if name != format!("/{}", CREATED_LIST) {
+            files.push(name);
+        }
+    }
+    Ok(())
+}
+
+/// Restores the files [`apply_mirror`] changed and removes the ones it
+/// created. Returns the number of files restored or removed.
+pub fn reset_mirror(rootfs: &Path) -> Result<usize, Box<dyn std::error::Error>> {
+    let backups = backup_dir(rootfs);
+    if !backups.exists() {
+        return Ok(0);
+    }
+
+    let created = fs::read_to_string(backups.join(CREATED_LIST)).unwrap_or_default();
+    let mut files = Vec::new();
+    backed_up_files(&backups, "", &mut files)?;
+    for file in &files {
+        let target = resolve_guest_path(rootfs, file);
+        if created.lines().any(|line| line == file) {
+            if let Some(path) = target {
+                fs::remove_file(path)?;
+            }
+        } else {
+            let target = target.unwrap_or_else(|| rootfs.join(file.trim_start_matches('/')));
+            fs::copy(backups.join(file.trim_start_matches('/')), target)?;
+        }
+    }
+
+    fs::remove_dir_all(&backups)?;
+    Ok(files.len())
+}
+
+#[cfg(test)]
+#[path = "mirror_tests.rs"]
+mod mirror_tests;
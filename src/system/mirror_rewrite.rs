@@ -0,0 +1,137 @@
+/// `url` moved onto `mirror`: everything up to the path segment the mirror
+/// ends with is replaced (`http://deb.debian.org/debian/` becomes
+/// `https://mirrors.163.com/debian/`), or just the scheme and host when the
+/// host is one of `upstream_hosts`. `None` when neither applies.
+pub fn rebase_url(url: &str, mirror: &str, upstream_hosts: &[&str]) -> Option<String> {
+    let mirror = mirror.trim_end_matches('/');
+    let (_, rest) = url.split_once("://")?;
+    let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+
+    let mirror_path = mirror.split_once("://")?.1.split_once('/').map(|(_, path)| path).unwrap_or_default();
+    if let Some(segment) = mirror_path.rsplit('/').next().filter(|segment| !segment.is_empty()) {
+        let mut offset = 0;
+        for part in path.split('/') {
+            if part == segment {
+                return Some(format!("{}{}", mirror, &path[offset + part.len()..]));
+            }
+            offset += part.len() + 1;
+        }
+    }
+
+    upstream_hosts.contains(&host).then(|| format!("{}/{}", mirror, path))
+}
+
+/// Applies `rewrite` to every line, keeping the rest of the file as it is.
+/// `None` if no line changed.
+fn rewrite_lines(content: &str, mut rewrite: impl FnMut(&str) -> Option<String>) -> Option<String> {
+    let mut changed = false;
+    let mut result = String::new();
+    for line in content.split_inclusive('\n') {
+        let (text, newline) = match line.strip_suffix('\n') {
+            Some(text) => (text, "\n"),
+            None => (line, ""),
+        };
+        match rewrite(text) {
+            Some(new) if new != text => {
+                changed = true;
+                result.push_str(&new);
+            }
+            _ => result.push_str(text),
+        }
+        result.push_str(newline);
+    }
+    changed.then_some(result)
+}
+
+/// One-line apt sources: `deb [options] uri suite components`.
+pub fn rewrite_sources_list(content: &str, mirror: &str) -> Option<String> {
+    rewrite_lines(content, |line| {
+        let mut words = line.split_whitespace();
+        if !matches!(words.next(), Some("deb") | Some("deb-src")) {
+            return None;
+        }
+        let mut uri = words.next()?;
+        if uri.starts_with('[') {
+            while !uri.ends_with(']') {
+                uri = words.next()?;
+            }
+            uri = words.next()?;
+        }
+        Some(line.replacen(uri, &rebase_url(uri, mirror, &[])?, 1))
+    })
+}
+
+/// deb822 apt sources (`debian.sources`, `ubuntu.sources`).
+pub fn rewrite_deb822(content: &str, mirror: &str) -> Option<String> {
+    rewrite_lines(content, |line| {
+        let uris = line.strip_prefix("URIs:")?;
+        let rebased: Vec<String> = uris
+            .split_whitespace()
+            .map(|uri| rebase_url(uri, mirror, &[]).unwrap_or_else(|| uri.to_string()))
+            .collect();
+        Some(format!("URIs: {}", rebased.join(" ")))
+    })
+}
+
+/// `/etc/apk/repositories`: one URL per line, optionally tagged `@name`.
+pub fn rewrite_apk(content: &str, mirror: &str) -> Option<String> {
+    rewrite_lines(content, |line| {
+        if line.trim_start().starts_with('#') {
+            return None;
+        }
+        let url = line.split_whitespace().last()?;
+        Some(line.replacen(url, &rebase_url(url, mirror, &[])?, 1))
+    })
+}
+
+/// dnf, yum and zypper `.repo` files: sections whose `baseurl` (commented out
+/// or not) moves to the mirror stop using `metalink` and `mirrorlist`.
+pub fn rewrite_repo_file(content: &str, mirror: &str, upstream_hosts: &[&str]) -> Option<String> {
+    let baseurl = |line: &str| {
+        let url = line.strip_prefix('#').unwrap_or(line).strip_prefix("baseurl=")?;
+        rebase_url(url.trim(), mirror, upstream_hosts).map(|url| format!("baseurl={}", url))
+    };
+
+    let mut section = 0;
+    let mut rebased_sections = Vec::new();
+    for line in content.lines() {
+        if line.starts_with('[') {
+            section += 1;
+        } else if baseurl(line).is_some() {
+            rebased_sections.push(section);
+        }
+    }
+
+    let mut section = 0;
+    rewrite_lines(content, |line| {
+        if line.starts_with('[') {
+            section += 1;
+        }
+        if !rebased_sections.contains(&section) {
+            return None;
+        }
+        if line.starts_with("metalink=") || line.starts_with("mirrorlist=") {
+            return Some(format!("#{}", line));
+        }
+        baseurl(line)
+    })
+}
+
+/// xbps `repository=<url>` entries.
+pub fn rewrite_xbps(content: &str, mirror: &str) -> Option<String> {
+    rewrite_lines(content, |line| {
+        let url = line.strip_prefix("repository=")?;
+        Some(format!("repository={}", rebase_url(url.trim(), mirror, &["repo-default.voidlinux.org"])?))
+    })
+}
+
+/// pacman uses the first `Server` of the mirrorlist. `mirror` may be a full
+/// server template; otherwise the Arch Linux layout is assumed.
+pub fn rewrite_pacman(content: &str, mirror: &str) -> Option<String> {
+    let server = if mirror.contains("$repo") {
+        mirror.to_string()
+    } else {
+        format!("{}/$repo/os/$arch", mirror.trim_end_matches('/'))
+    };
+    Some(format!("# insOs mirror\nServer = {}\n\n{}", server, content))
+}
@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod tests {
+    use crate::system::mirror::*;
+    use crate::system::mirror_rewrite::rebase_url;
+    use tempfile::TempDir;
+
+    const MIRROR: &str = "https://mirrors.example.com/debian/";
+
+    fn write(rootfs: &Path, path: &str, content: &str) {
+        let path = rootfs.join(path.trim_start_matches('/'));
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn read(rootfs: &Path, path: &str) -> String {
+        fs::read_to_string(rootfs.join(path.trim_start_matches('/'))).unwrap()
+    }
+
+    #[test]
+    fn test_rebase_url() {
+        assert_eq!(rebase_url("http://deb.debian.org/debian", MIRROR, &[]).unwrap(), "https://mirrors.example.com/debian");
+        assert_eq!(
+            rebase_url("http://download.example/pub/fedora/linux/releases/$releasever/", "https://mirrors.example.com/fedora", &[]).unwrap(),
+            "https://mirrors.example.com/fedora/linux/releases/$releasever/"
+        );
+        assert_eq!(rebase_url("http://security.debian.org/debian-security", MIRROR, &[]), None);
+        assert_eq!(
+            rebase_url("http://download.opensuse.org/tumbleweed/repo/oss/", "https://mirrors.example.com/opensuse", &["download.opensuse.org"]).unwrap(),
+            "https://mirrors.example.com/opensuse/tumbleweed/repo/oss/"
+        );
+    }
+
+    #[test]
+    fn test_apply_and_reset_apt() {
+        let temp_dir = TempDir::new().unwrap();
+        let rootfs = temp_dir.path();
+        let sources = "Types: deb\nURIs: http://deb.debian.org/debian\nSuites: trixie trixie-updates\n\nTypes: deb\nURIs: http://security.debian.org/debian-security\nSuites: trixie-security\n";
+        write(rootfs, "/etc/apt/sources.list.d/debian.sources", sources);
+        write(rootfs, "/etc/apt/sources.list", "# empty\ndeb [arch=arm64 signed-by=/k.gpg] http://deb.debian.org/debian trixie main\n");
+
+        assert_eq!(apply_mirror(rootfs, PackageManager::Apt, MIRROR).unwrap(), 2);
+        assert_eq!(read(rootfs, "/etc/apt/sources.list"), "# empty\ndeb [arch=arm64 signed-by=/k.gpg] https://mirrors.example.com/debian trixie main\n");
+        let rewritten = read(rootfs, "/etc/apt/sources.list.d/debian.sources");
+        assert!(rewritten.contains("URIs: https://mirrors.example.com/debian\n"));
+        assert!(rewritten.contains("URIs: http://security.debian.org/debian-security\n"));
+
+        apply_mirror(rootfs, PackageManager::Apt, "https://other.example.com/debian").unwrap();
+        assert!(read(rootfs, "/etc/apt/sources.list").contains("https://other.example.com/debian trixie"));
+
+        assert_eq!(reset_mirror(rootfs).unwrap(), 2);
+        assert_eq!(read(rootfs, "/etc/apt/sources.list.d/debian.sources"), sources);
+        assert!(!backup_dir(rootfs).exists());
+        assert_eq!(reset_mirror(rootfs).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_apply_repo_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let rootfs = temp_dir.path();
+        write(rootfs, "/etc/yum.repos.d/fedora.repo", "[fedora]\nname=Fedora\nmetalink=https://mirrors.fedoraproject.org/metalink?repo=fedora\n#baseurl=http://download.example/pub/fedora/linux/releases/$releasever/Everything/$basearch/os/\n\n[other]\nmetalink=https://example.org/other\n");
+
+        assert_eq!(apply_mirror(rootfs, PackageManager::Dnf, "https://mirrors.example.com/fedora/").unwrap(), 1);
+        assert_eq!(
+            read(rootfs, "/etc/yum.repos.d/fedora.repo"),
+            "[fedora]\nname=Fedora\n#metalink=https://mirrors.fedoraproject.org/metalink?repo=fedora\nbaseurl=https://mirrors.example.com/fedora/linux/releases/$releasever/Everything/$basearch/os/\n\n[other]\nmetalink=https://example.org/other\n"
+        );
+
+        assert_eq!(apply_mirror(rootfs, PackageManager::Apk, "https://mirrors.example.com/alpine").unwrap(), 0);
+    }
+
+    #[test]
+    fn test_apply_xbps_creates_override() {
+        let temp_dir = TempDir::new().unwrap();
+        let rootfs = temp_dir.path();
+        write(rootfs, "/usr/share/xbps.d/00-repository-main.conf", "repository=https://repo-default.voidlinux.org/current\n");
+
+        apply_mirror(rootfs, PackageManager::Xbps, "https://mirrors.example.com/void").unwrap();
+        assert_eq!(apply_mirror(rootfs, PackageManager::Xbps, "https://mirrors.example.com/voidlinux").unwrap(), 1);
+        assert_eq!(read(rootfs, "/etc/xbps.d/00-repository-main.conf"), "repository=https://mirrors.example.com/voidlinux/current\n");
+
+        assert_eq!(reset_mirror(rootfs).unwrap(), 1);
+        assert!(!rootfs.join("etc/xbps.d/00-repository-main.conf").exists());
+        assert!(rootfs.join("usr/share/xbps.d/00-repository-main.conf").exists());
+    }
+
+    #[test]
+    fn test_apply_pacman() {
+        let temp_dir = TempDir::new().unwrap();
+        let rootfs = temp_dir.path();
+        write(rootfs, "/etc/pacman.d/mirrorlist", "Server = http://mirror.archlinuxarm.org/$arch/$repo\n");
+
+        apply_mirror(rootfs, PackageManager::Pacman, "https://mirrors.example.com/archlinuxarm/$arch/$repo").unwrap();
+        apply_mirror(rootfs, PackageManager::Pacman, "https://mirrors.example.com/archlinux/").unwrap();
+        let mirrorlist = read(rootfs, "/etc/pacman.d/mirrorlist");
+        assert!(mirrorlist.starts_with("# insOs mirror\nServer = https://mirrors.example.com/archlinux/$repo/os/$arch\n"));
+        assert_eq!(mirrorlist.matches("Server =").count(), 2);
+    }
+}
@@ -1,4 +1,20 @@
 pub mod manage;
+pub mod launcher;
+pub mod backend;
 pub mod permissions;
+pub mod session;
+pub mod services;
+pub mod desktop;
+pub mod audio;
+pub mod volumes;
+pub mod shortcuts;
+pub mod ssh;
+pub mod disk_usage;
+pub mod info;
+pub mod check;
+pub mod check_items;
+pub mod repair;
+pub mod mirror;
+pub mod mirror_rewrite;
 
 pub use manage::*;
@@ -0,0 +1,119 @@
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use crate::config::ConfigManager;
+use crate::distro::meta::META_FILE;
+use crate::distro::SystemMeta;
+use crate::i18n::Translator;
+use crate::system::backend::configured_backend;
+use crate::system::check::{check_system, CheckItem, CheckKind, Problem};
+use crate::system::info::read_os_release;
+use crate::system::launcher::{write_start_script, GUEST_HOME};
+use crate::utils::arch::{detect_architecture, elf_architecture};
+use crate::utils::fs::{get_system_dir, load_system_meta, resolve_guest_path, save_system_meta};
+
+pub(super) const NAMESERVERS: &str = "nameserver 8.8.8.8\nnameserver 1.1.1.1\n";
+pub(super) const LOCALHOST_ENTRIES: &str = "127.0.0.1 localhost\n::1 localhost ip6-localhost ip6-loopback\n";
+
+/// Metadata reconstructed from the rootfs when `meta.toml` is lost: the
+/// distro from os-release and the architecture from the ELF header of `/bin/sh`.
+pub fn rebuild_meta(system_dir: &Path, system_id: &str, config_manager: &ConfigManager) -> Result<SystemMeta, Box<dyn std::error::Error>> {
+    let os_id = read_os_release(system_dir)
+        .and_then(|fields| fields.get("ID").cloned())
+        .unwrap_or_default();
+    let distro = crate::distro::get_all_distros()
+        .into_iter()
+        .map(|d| d.name.as_str().to_string())
+        .find(|name| *name == os_id || (os_id == "arch" && name == "archlinux") || (os_id.starts_with("opensuse") && name == "opensuse"))
+        .unwrap_or(os_id);
+
+    let host = detect_architecture()?;
+    let arch = resolve_guest_path(system_dir, "/bin/sh")
+        .and_then(|path| elf_architecture(&path))
+        .unwrap_or(host);
+
+    let mut meta = SystemMeta::new(system_id.to_string(), distro, arch);
+    meta.emulated = !host.can_run_natively(&arch);
+    meta.backend = configured_backend(config_manager, system_id)?;
+    Ok(meta)
+}
+
+pub(super) fn set_mode(path: &Path, mode: u32) -> Result<(), Box<dyn std::error::Error>> {
+    fs::set_permissions(path, fs::Permissions::from_mode(mode))?;
+    Ok(())
+}
+
+/// Writes a guest config file, replacing a dangling symlink (e.g. to
+/// systemd-resolved's stub file) instead of following it.
+fn write_guest_file(rootfs: &Path, guest_path: &str, content: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let path = rootfs.join(guest_path.trim_start_matches('/'));
+    if fs::symlink_metadata(&path).is_ok_and(|meta| meta.file_type().is_symlink()) {
+        fs::remove_file(&path)?;
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, content)?;
+    Ok(())
+}
+
+pub(super) fn repair_item(system_dir: &Path, system_id: &str, item: &CheckItem, config_manager: &ConfigManager) -> Result<(), Box<dyn std::error::Error>> {
+    let guest = |path: &str| resolve_guest_path(system_dir, path).unwrap_or_else(|| system_dir.join(path.trim_start_matches('/')));
+
+    match item.kind {
+        CheckKind::Metadata if item.problem == Some(Problem::Outdated) => {
+            load_system_meta(system_id)?;
+        }
+        CheckKind::Metadata => {
+            let meta_path = system_dir.join(META_FILE);
+            if meta_path.exists() {
+                fs::rename(&meta_path, meta_path.with_extension("toml.broken"))?;
+            }
+            save_system_meta(system_id, &rebuild_meta(system_dir, system_id, config_manager)?)?;
+        }
+        CheckKind::Launcher => write_start_script(system_dir, system_id)?,
+        CheckKind::Executable => {
+            let path = guest(&item.subject);
+            let mode = fs::metadata(&path)?.permissions().mode();
+            set_mode(&path, mode | 0o755)?;
+        }
+        CheckKind::TmpDir => {
+            fs::create_dir_all(guest("/tmp"))?;
+            set_mode(&guest("/tmp"), 0o1777)?;
+        }
+        CheckKind::HomeDir => {
+            fs::create_dir_all(guest(GUEST_HOME))?;
+            set_mode(&guest(GUEST_HOME), 0o700)?;
+        }
+        CheckKind::Resolver => write_guest_file(system_dir, "/etc/resolv.conf", NAMESERVERS)?,
+        CheckKind::Hosts => {
+            let mut content = resolve_guest_path(system_dir, "/etc/hosts")
+                .and_then(|path| fs::read_to_string(path).ok())
+                .unwrap_or_default();
+            if !content.is_empty() && !content.ends_with('\n') {
+                content.push('\n');
+            }
+            content.push_str(LOCALHOST_ENTRIES);
+            write_guest_file(system_dir, "/etc/hosts", &content)?;
+        }
+        CheckKind::OsRelease => {}
+    }
+    Ok(())
+}
+
+/// Fixes every repairable problem found by [`check_system`]. Returns the
+/// repaired items and the result of checking again.
+pub fn repair_system(system_id: &str, translator: &Translator) -> Result<(Vec<CheckItem>, Vec<CheckItem>), Box<dyn std::error::Error>> {
+    let system_dir = get_system_dir(system_id)?;
+    let config_manager = ConfigManager::new()?;
+    let mut repaired = Vec::new();
+
+    for item in check_system(system_id, translator)? {
+        if item.is_repairable() {
+            repair_item(&system_dir, system_id, &item, &config_manager)?;
+            repaired.push(item);
+        }
+    }
+
+    Ok((repaired, check_system(system_id, translator)?))
+}
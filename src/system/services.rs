@@ -0,0 +1,162 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use crate::config::ConfigManager;
+use crate::i18n::Translator;
+use crate::system::backend::{spawn_detached, teardown, ContainerBackend, LaunchSpec};
+use crate::system::launcher::system_backend_and_spec;
+use crate::system::session::{is_system_running, read_live_record, write_process_record, Session, STATE_DIR};
+use crate::utils::process::terminate_process_trees;
+
+pub const SERVICE_STOP_GRACE_PERIOD: Duration = Duration::from_secs(5);
+
+pub fn services_dir(system_dir: &Path) -> PathBuf {
+    system_dir.join(STATE_DIR).join("services")
+}
+
+pub fn service_log_path(system_dir: &Path, name: &str) -> PathBuf {
+    system_dir.join(STATE_DIR).join("logs").join(format!("{}.log", name))
+}
+
+pub fn service_status(system_dir: &Path, name: &str) -> Option<Session> {
+    read_live_record(&services_dir(system_dir).join(name))
+}
+
+pub fn running_services(system_dir: &Path) -> Vec<(String, Session)> {
+    let Ok(entries) = fs::read_dir(services_dir(system_dir)) else {
+        return Vec::new();
+    };
+
+    let mut services: Vec<(String, Session)> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_str()?.to_string();
+            Some((name, read_live_record(&entry.path())?))
+        })
+        .collect();
+    services.sort_by(|a, b| a.0.cmp(&b.0));
+    services
+}
+
+pub fn has_running_services(system_dir: &Path) -> bool {
+    !running_services(system_dir).is_empty()
+}
+
+/// `service.<name>` from the config, or the name itself for services listed
+/// in `services` without an explicit command.
+pub fn resolve_service_command(config_manager: &ConfigManager, system_id: &str, name: &str) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    if let Some(command) = config_manager.get_service_command(system_id, name)? {
+        return Ok(Some(command));
+    }
+
+    let listed = config_manager.get_services_for_system(system_id)?.iter().any(|service| service == name);
+    Ok(listed.then(|| name.to_string()))
+}
+
+/// Spawns the service command inside the rootfs in the background. The
+/// command must stay in the foreground (e.g. `sshd -D`) to be tracked.
+pub fn spawn_service(backend: &dyn ContainerBackend, spec: &LaunchSpec, name: &str, command: &str) -> Result<Session, Box<dyn std::error::Error>> {
+    let mut spec = spec.clone();
+    spec.argv = vec!["/bin/sh".to_string(), "-c".to_string(), command.to_string()];
+
+    let child = spawn_detached(backend, &spec, &service_log_path(&spec.rootfs, name))?;
+    write_process_record(&services_dir(&spec.rootfs).join(name), child.id(), command)
+}
+
+/// Returns false when the service was not running.
+pub fn stop_service(system_dir: &Path, name: &str) -> bool {
+    let Some(session) = service_status(system_dir, name) else {
+        return false;
+    };
+
+    terminate_process_trees(&[session.pid], SERVICE_STOP_GRACE_PERIOD);
+    let _ = fs::remove_file(services_dir(system_dir).join(name));
+    true
+}
+
+pub fn stop_all_services(system_dir: &Path) -> usize {
+    running_services(system_dir)
+        .iter()
+        .filter(|(name, _)| stop_service(system_dir, name))
+        .count()
+}
+
+/// Starts every configured service that is not already running; failures
+/// are reported but do not prevent the login.
+pub fn start_configured_services(
+    system_id: &str,
+    config_manager: &ConfigManager,
+    backend: &dyn ContainerBackend,
+    spec: &LaunchSpec,
+    translator: &Translator,
+) -> Result<(), Box<dyn std::error::Error>> {
+    for name in config_manager.get_services_for_system(system_id)? {
+        if service_status(&spec.rootfs, &name).is_some() {
+            continue;
+        }
+
+        let command = resolve_service_command(config_manager, system_id, &name)?.unwrap_or_else(|| name.clone());
+        if let Err(e) = spawn_service(backend, spec, &name, &command) {
+            eprintln!("{}", translator.t_fmt("service_start_failed", &[&name, &e.to_string()]));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn start_system_service(system_id: &str, name: &str, translator: &Translator) -> Result<Session, Box<dyn std::error::Error>> {
+    let (backend, spec) = system_backend_and_spec(system_id, translator)?;
+    if let Some(session) = service_status(&spec.rootfs, name) {
+        return Ok(session);
+    }
+
+    let config_manager = ConfigManager::new()?;
+    let command = resolve_service_command(&config_manager, system_id, name)?
+        .ok_or_else(|| translator.t_fmt("service_not_configured", &[name, system_id]))?;
+    spawn_service(backend.as_ref(), &spec, name, &command)
+}
+
+/// Stops the service and releases the backend's mounts if nothing else uses them.
+pub fn stop_system_service(system_id: &str, name: &str, translator: &Translator) -> Result<bool, Box<dyn std::error::Error>> {
+    let (backend, spec) = system_backend_and_spec(system_id, translator)?;
+    let stopped = stop_service(&spec.rootfs, name);
+    if !is_system_running(&spec.rootfs) {
+        teardown(backend.as_ref(), &spec);
+    }
+    Ok(stopped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_service_records_track_running_processes() {
+        let temp_dir = TempDir::new().unwrap();
+        let system_dir = temp_dir.path();
+        assert!(!has_running_services(system_dir));
+
+        let mut child = Command::new("sleep").arg("30").spawn().unwrap();
+        write_process_record(&services_dir(system_dir).join("sleeper"), child.id(), "sleep 30").unwrap();
+
+        let services = running_services(system_dir);
+        assert_eq!(services.len(), 1);
+        assert_eq!(services[0].0, "sleeper");
+        assert_eq!(services[0].1.command, "sleep 30");
+
+        assert!(stop_service(system_dir, "sleeper"));
+        child.wait().unwrap();
+        assert!(!stop_service(system_dir, "sleeper"));
+        assert!(service_status(system_dir, "sleeper").is_none());
+    }
+
+    #[test]
+    fn test_service_log_path() {
+        assert_eq!(
+            service_log_path(Path::new("/data/termos/debian1"), "sshd"),
+            PathBuf::from("/data/termos/debian1/.insos/logs/sshd.log")
+        );
+    }
+}
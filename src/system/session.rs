@@ -0,0 +1,216 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use crate::utils::fs::{get_installed_systems, get_system_dir};
+use crate::utils::process::{get_process_start_time, is_process_alive, terminate_process_trees};
+
+pub const STATE_DIR: &str = ".insos";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Session {
+    pub pid: u32,
+    pub proc_start: Option<u64>,
+    pub started_at: String,
+    pub command: String,
+}
+
+impl fmt::Display for Session {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "pid = {}", self.pid)?;
+        if let Some(start) = self.proc_start {
+            writeln!(f, "proc_start = {}", start)?;
+        }
+        writeln!(f, "started_at = {}", self.started_at)?;
+        writeln!(f, "command = {}", self.command)
+    }
+}
+
+impl Session {
+    pub fn from_string(content: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let map: HashMap<&str, &str> = content
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, value)| (key.trim(), value.trim()))
+            .collect();
+
+        let pid = map.get("pid").ok_or("会话文件缺少 pid")?.parse()?;
+        let proc_start = match map.get("proc_start") {
+            Some(value) => Some(value.parse()?),
+            None => None,
+        };
+
+        Ok(Session {
+            pid,
+            proc_start,
+            started_at: map.get("started_at").unwrap_or(&"").to_string(),
+            command: map.get("command").unwrap_or(&"").to_string(),
+        })
+    }
+
+    pub fn is_alive(&self) -> bool {
+        is_process_alive(self.pid, self.proc_start)
+    }
+}
+
+pub fn sessions_dir(system_dir: &Path) -> PathBuf {
+    system_dir.join(STATE_DIR).join("sessions")
+}
+
+/// Removes the session file when the launch it describes ends.
+pub struct SessionGuard {
+    path: PathBuf,
+}
+
+impl Drop for SessionGuard {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Writes a process record (PID, start time, command) to `path`.
+pub fn write_process_record(path: &Path, pid: u32, command: &str) -> Result<Session, Box<dyn std::error::Error>> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let session = Session {
+        pid,
+        proc_start: get_process_start_time(pid),
+        started_at: chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+        command: command.to_string(),
+    };
+
+    fs::write(path, session.to_string())?;
+    Ok(session)
+}
+
+/// Reads a process record, deleting it if the process has exited.
+pub fn read_live_record(path: &Path) -> Option<Session> {
+    let session = fs::read_to_string(path)
+        .ok()
+        .and_then(|content| Session::from_string(&content).ok());
+
+    match session {
+        Some(session) if session.is_alive() => Some(session),
+        _ => {
+            let _ = fs::remove_file(path);
+            None
+        }
+    }
+}
+
+pub fn record_session(system_dir: &Path, pid: u32, argv: &[String]) -> Result<SessionGuard, Box<dyn std::error::Error>> {
+    let path = sessions_dir(system_dir).join(pid.to_string());
+    write_process_record(&path, pid, &argv.join(" "))?;
+    Ok(SessionGuard { path })
+}
+
+/// Live sessions of one system; files left behind by exited processes are deleted.
+pub fn list_sessions(system_dir: &Path) -> Result<Vec<Session>, Box<dyn std::error::Error>> {
+    let dir = sessions_dir(system_dir);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut sessions = Vec::new();
+    for entry in fs::read_dir(&dir)? {
+        if let Some(session) = read_live_record(&entry?.path()) {
+            sessions.push(session);
+        }
+    }
+
+    sessions.sort_by_key(|session| session.pid);
+    Ok(sessions)
+}
+
+pub fn list_all_sessions() -> Result<Vec<(String, Session)>, Box<dyn std::error::Error>> {
+    let mut systems = get_installed_systems()?;
+    systems.sort();
+    
+    let mut result = Vec::new();
+    for system_id in systems {
+        for session in list_sessions(&get_system_dir(&system_id)?)? {
+            result.push((system_id.clone(), session));
+        }
+    }
+    Ok(result)
+}
+
+pub fn is_system_running(system_dir: &Path) -> bool {
+    list_sessions(system_dir).map(|sessions| !sessions.is_empty()).unwrap_or(false)
+}
+
+/// Terminates the process tree of every session. Returns the number of
+/// sessions stopped.
+pub fn stop_sessions(system_dir: &Path, grace: Duration) -> Result<usize, Box<dyn std::error::Error>> {
+    let sessions = list_sessions(system_dir)?;
+    let pids: Vec<u32> = sessions.iter().map(|session| session.pid).collect();
+    terminate_process_trees(&pids, grace);
+
+    for session in &sessions {
+        let _ = fs::remove_file(sessions_dir(system_dir).join(session.pid.to_string()));
+    }
+
+    Ok(sessions.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_session_roundtrip() {
+        let session = Session {
+            pid: 42,
+            proc_start: Some(1234),
+            started_at: "2025-01-01T00:00:00Z".to_string(),
+            command: "/bin/bash --login".to_string(),
+        };
+
+        assert_eq!(Session::from_string(&session.to_string()).unwrap(), session);
+        assert!(Session::from_string("command = x\n").is_err());
+    }
+
+    #[test]
+    fn test_record_and_list_sessions() {
+        let temp_dir = TempDir::new().unwrap();
+        let argv = vec!["/bin/sh".to_string()];
+
+        let guard = record_session(temp_dir.path(), std::process::id(), &argv).unwrap();
+        let sessions = list_sessions(temp_dir.path()).unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].pid, std::process::id());
+        assert_eq!(sessions[0].command, "/bin/sh");
+        assert!(is_system_running(temp_dir.path()));
+
+        drop(guard);
+        assert!(!is_system_running(temp_dir.path()));
+    }
+
+    #[test]
+    fn test_stale_sessions_are_pruned() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = sessions_dir(temp_dir.path());
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("999999999"), "pid = 999999999\nstarted_at = x\ncommand = sh\n").unwrap();
+
+        assert!(list_sessions(temp_dir.path()).unwrap().is_empty());
+        assert!(!dir.join("999999999").exists());
+    }
+
+    #[test]
+    fn test_stop_sessions_kills_process_tree() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut child = Command::new("sh").args(["-c", "sleep 30 & wait"]).spawn().unwrap();
+        let _guard = record_session(temp_dir.path(), child.id(), &["sh".to_string()]).unwrap();
+
+        let stopped = stop_sessions(temp_dir.path(), Duration::from_secs(2)).unwrap();
+        assert_eq!(stopped, 1);
+        child.wait().unwrap();
+        assert!(list_sessions(temp_dir.path()).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,134 @@
+use std::fs;
+use std::path::PathBuf;
+use crate::system::launcher::{current_insos_path, render_start_script};
+use crate::utils::cmd::shell_quote;
+use crate::utils::fs::get_home_dir;
+
+const RC_FILES: [&str; 2] = [".bashrc", ".zshrc"];
+
+/// Termux:Widget lists the scripts in `~/.shortcuts`.
+pub fn shortcut_path(system_id: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    Ok(get_home_dir()?.join(".shortcuts").join(format!("login-{}", system_id)))
+}
+
+/// The rc file of the user's login shell: `~/.zshrc` for zsh, `~/.bashrc` otherwise.
+pub fn shell_rc_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let shell = std::env::var("SHELL").unwrap_or_default();
+    let rc = if shell.ends_with("zsh") { RC_FILES[1] } else { RC_FILES[0] };
+    Ok(get_home_dir()?.join(rc))
+}
+
+fn alias_markers(system_id: &str) -> (String, String) {
+    (
+        format!("# >>> insOs login-{} >>>", system_id),
+        format!("# <<< insOs login-{} <<<", system_id),
+    )
+}
+
+pub fn remove_alias_block(content: &str, system_id: &str) -> String {
+    let (begin, end) = alias_markers(system_id);
+    let mut inside = false;
+    let mut lines = Vec::new();
+
+    for line in content.lines() {
+        if line.trim() == begin {
+            inside = true;
+        } else if line.trim() == end {
+            inside = false;
+        } else if !inside {
+            lines.push(line);
+        }
+    }
+
+    let mut result = lines.join("\n");
+    if !result.is_empty() {
+        result.push('\n');
+    }
+    result
+}
+
+/// Appends (or replaces) a `login-<id>` shell function wrapped in markers so
+/// it can be removed again without touching the rest of the file.
+pub fn add_alias_block(content: &str, system_id: &str, insos_path: &str) -> String {
+    let (begin, end) = alias_markers(system_id);
+    let mut result = remove_alias_block(content, system_id);
+    result.push_str(&format!(
+        "{}\nlogin-{}() {{ {} login {} \"$@\"; }}\n{}\n",
+        begin,
+        system_id,
+        shell_quote(insos_path),
+        shell_quote(system_id),
+        end
+    ));
+    result
+}
+
+pub fn create_shortcut(system_id: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = shortcut_path(system_id)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, render_start_script(system_id, &current_insos_path()))?;
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755))?;
+    Ok(path)
+}
+
+pub fn create_shell_alias(system_id: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let rc_path = shell_rc_path()?;
+    let content = fs::read_to_string(&rc_path).unwrap_or_default();
+    fs::write(&rc_path, add_alias_block(&content, system_id, &current_insos_path()))?;
+    Ok(rc_path)
+}
+
+/// Removes the widget script and the alias from every known rc file.
+pub fn remove_shortcuts(system_id: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let path = shortcut_path(system_id)?;
+    if path.exists() {
+        fs::remove_file(&path)?;
+    }
+
+    let home = get_home_dir()?;
+    for rc in RC_FILES {
+        let rc_path = home.join(rc);
+        let Ok(content) = fs::read_to_string(&rc_path) else {
+            continue;
+        };
+        let cleaned = remove_alias_block(&content, system_id);
+        if cleaned != content {
+            fs::write(&rc_path, cleaned)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_alias_block_replaces_existing() {
+        let content = add_alias_block("export A=1\n", "debian1", "/usr/bin/insOs");
+        assert_eq!(
+            content,
+            "export A=1\n# >>> insOs login-debian1 >>>\nlogin-debian1() { '/usr/bin/insOs' login 'debian1' \"$@\"; }\n# <<< insOs login-debian1 <<<\n"
+        );
+
+        let again = add_alias_block(&content, "debian1", "/usr/bin/insOs");
+        assert_eq!(again, content);
+    }
+
+    #[test]
+    fn test_remove_alias_block_keeps_other_systems() {
+        let content = add_alias_block("export A=1\n", "debian1", "insOs");
+        let content = add_alias_block(&content, "arch1", "insOs");
+
+        let cleaned = remove_alias_block(&content, "debian1");
+        assert!(!cleaned.contains("login-debian1"));
+        assert!(cleaned.starts_with("export A=1\n"));
+        assert!(cleaned.contains("login-arch1()"));
+        assert_eq!(remove_alias_block(&cleaned, "arch1"), "export A=1\n");
+    }
+}
@@ -0,0 +1,209 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use crate::distro::{package_manager_for, PackageManager};
+use crate::i18n::Translator;
+use crate::system::launcher::{run_guest_command, system_backend_and_spec, GUEST_HOME};
+use crate::system::services::{service_status, spawn_service, stop_service};
+use crate::utils::cmd::run_command;
+use crate::utils::fs::{get_home_dir, load_system_meta, save_system_meta};
+use crate::utils::net::{find_free_port, is_port_free};
+
+pub const SSH_SERVICE: &str = "sshd";
+pub const SSH_PORT_RANGE: (u16, u16) = (2222, 2299);
+/// Privilege separation needs `/run/sshd`, which is not part of most rootfs tarballs.
+const SSHD_COMMAND: &str = "mkdir -p /run/sshd && exec /usr/sbin/sshd -D -e";
+
+pub fn server_package(manager: PackageManager) -> &'static str {
+    match manager {
+        PackageManager::Apt | PackageManager::Dnf | PackageManager::Yum => "openssh-server",
+        PackageManager::Pacman | PackageManager::Apk | PackageManager::Zypper | PackageManager::Xbps => "openssh",
+    }
+}
+
+/// Sets `key value` in an sshd_config, reusing the first (possibly commented)
+/// occurrence and dropping later active ones.
+pub fn set_sshd_option(content: &str, key: &str, value: &str) -> String {
+    let entry = format!("{} {}", key, value);
+    let mut replaced = false;
+    let mut lines = Vec::new();
+
+    for line in content.lines() {
+        let active = line.trim();
+        let uncommented = active.trim_start_matches('#').trim();
+        let matches = uncommented.split_whitespace().next() == Some(key);
+
+        if matches && !replaced {
+            lines.push(entry.clone());
+            replaced = true;
+        } else if !matches || active.starts_with('#') {
+            lines.push(line.to_string());
+        }
+    }
+
+    if !replaced {
+        lines.push(entry);
+    }
+
+    let mut result = lines.join("\n");
+    result.push('\n');
+    result
+}
+
+pub fn configure_sshd(rootfs: &Path, port: u16) -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = rootfs.join("etc/ssh/sshd_config");
+    let mut content = fs::read_to_string(&config_path)
+        .map_err(|e| format!("无法读取 {}: {}", config_path.display(), e))?;
+
+    for (key, value) in [
+        ("Port", port.to_string()),
+        ("PermitRootLogin", "prohibit-password".to_string()),
+        ("PasswordAuthentication", "no".to_string()),
+    ] {
+        content = set_sshd_option(&content, key, &value);
+    }
+
+    fs::write(&config_path, content)?;
+    Ok(())
+}
+
+/// Appends keys to the guest root's `authorized_keys`, skipping ones already present.
+pub fn authorize_keys(rootfs: &Path, keys: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let ssh_dir = rootfs.join(GUEST_HOME.trim_start_matches('/')).join(".ssh");
+    fs::create_dir_all(&ssh_dir)?;
+    fs::set_permissions(&ssh_dir, fs::Permissions::from_mode(0o700))?;
+
+    let path = ssh_dir.join("authorized_keys");
+    let mut content = fs::read_to_string(&path).unwrap_or_default();
+    for key in keys.iter().map(|key| key.trim()).filter(|key| !key.is_empty()) {
+        if !content.lines().any(|line| line.trim() == key) {
+            if !content.is_empty() && !content.ends_with('\n') {
+                content.push('\n');
+            }
+            content.push_str(key);
+            content.push('\n');
+        }
+    }
+
+    fs::write(&path, content)?;
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o600))?;
+    Ok(())
+}
+
+/// The host user's public key, generating an ed25519 key pair if there is none.
+pub fn host_public_key() -> Result<String, Box<dyn std::error::Error>> {
+    let ssh_dir = get_home_dir()?.join(".ssh");
+    let existing = ["id_ed25519.pub", "id_ecdsa.pub", "id_rsa.pub"]
+        .iter()
+        .map(|name| ssh_dir.join(name))
+        .find(|path| path.exists());
+
+    let path = match existing {
+        Some(path) => path,
+        None => {
+            fs::create_dir_all(&ssh_dir)?;
+            let key = ssh_dir.join("id_ed25519");
+            run_command(&format!("ssh-keygen -q -t ed25519 -N '' -f '{}'", key.display()))?;
+            key.with_extension("pub")
+        }
+    };
+
+    Ok(fs::read_to_string(path)?)
+}
+
+fn ensure_sshd_running(system_id: &str, port: u16, translator: &Translator) -> Result<(), Box<dyn std::error::Error>> {
+    let (backend, spec) = system_backend_and_spec(system_id, translator)?;
+    if service_status(&spec.rootfs, SSH_SERVICE).is_none() {
+        if !is_port_free(port) {
+            return Err(translator.t_fmt("ssh_port_in_use", &[&port.to_string()]).into());
+        }
+        spawn_service(backend.as_ref(), &spec, SSH_SERVICE, SSHD_COMMAND)?;
+    }
+    Ok(())
+}
+
+/// Installs and configures sshd inside the guest and starts it as the
+/// `sshd` service. Returns the port it listens on.
+pub fn setup_ssh(system_id: &str, extra_key: Option<&PathBuf>, translator: &Translator) -> Result<u16, Box<dyn std::error::Error>> {
+    let (backend, spec) = system_backend_and_spec(system_id, translator)?;
+    let rootfs = spec.rootfs.clone();
+
+    if !rootfs.join("usr/sbin/sshd").exists() {
+        let distro = load_system_meta(system_id)?.distro;
+        let manager = package_manager_for(&rootfs, &distro)
+            .ok_or_else(|| translator.t_fmt("package_manager_not_found", &[system_id]))?;
+        println!("{}", translator.t_fmt("installing_ssh_server", &[server_package(manager), manager.as_str()]));
+        run_guest_command(backend.as_ref(), &spec, &manager.install_command(&[server_package(manager)]))?;
+    }
+    run_guest_command(backend.as_ref(), &spec, "ssh-keygen -A")?;
+
+    stop_service(&rootfs, SSH_SERVICE);
+    let mut meta = load_system_meta(system_id)?;
+    let port = match meta.ssh_port {
+        Some(port) if is_port_free(port) => port,
+        _ => find_free_port(SSH_PORT_RANGE.0, SSH_PORT_RANGE.1).ok_or_else(|| translator.t("no_free_port"))?,
+    };
+
+    configure_sshd(&rootfs, port)?;
+    let mut keys = vec![host_public_key()?];
+    if let Some(path) = extra_key {
+        keys.push(fs::read_to_string(path)?);
+    }
+    authorize_keys(&rootfs, &keys)?;
+
+    meta.ssh_port = Some(port);
+    save_system_meta(system_id, &meta)?;
+
+    ensure_sshd_running(system_id, port, translator)?;
+    Ok(port)
+}
+
+/// Connects to the guest's sshd from the host, starting it if needed.
+pub fn connect_ssh(system_id: &str, ssh_args: &[String], translator: &Translator) -> Result<i32, Box<dyn std::error::Error>> {
+    let port = load_system_meta(system_id)?
+        .ssh_port
+        .ok_or_else(|| translator.t_fmt("ssh_not_configured", &[system_id]))?;
+    ensure_sshd_running(system_id, port, translator)?;
+
+    let status = Command::new("ssh")
+        .args(["-p", &port.to_string(), "root@127.0.0.1"])
+        .args(ssh_args)
+        .status()?;
+    Ok(status.code().unwrap_or(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_set_sshd_option() {
+        let content = "Include /etc/ssh/sshd_config.d/*.conf\n#Port 22\nPort 2022\nUsePAM yes\n";
+        assert_eq!(
+            set_sshd_option(content, "Port", "2222"),
+            "Include /etc/ssh/sshd_config.d/*.conf\nPort 2222\nUsePAM yes\n"
+        );
+        assert_eq!(set_sshd_option("UsePAM yes\n", "PasswordAuthentication", "no"), "UsePAM yes\nPasswordAuthentication no\n");
+    }
+
+    #[test]
+    fn test_authorize_keys_deduplicates() {
+        let temp_dir = TempDir::new().unwrap();
+        let key = "ssh-ed25519 AAAA user@termux".to_string();
+
+        authorize_keys(temp_dir.path(), std::slice::from_ref(&key)).unwrap();
+        authorize_keys(temp_dir.path(), &[format!("{}\n", key), "ssh-rsa BBBB laptop".to_string()]).unwrap();
+
+        let content = fs::read_to_string(temp_dir.path().join("root/.ssh/authorized_keys")).unwrap();
+        assert_eq!(content, "ssh-ed25519 AAAA user@termux\nssh-rsa BBBB laptop\n");
+    }
+
+    #[test]
+    fn test_server_package() {
+        assert_eq!(server_package(PackageManager::Apt), "openssh-server");
+        assert_eq!(server_package(PackageManager::Pacman), "openssh");
+    }
+}
@@ -0,0 +1,135 @@
+use std::fs;
+use std::path::PathBuf;
+use crate::config::ConfigManager;
+use crate::utils::fs::{get_home_dir, get_installed_systems};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VolumeMount {
+    pub name: String,
+    pub guest_path: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VolumeInfo {
+    pub name: String,
+    pub host_path: PathBuf,
+    /// `(system_id, guest_path)` of every system attaching the volume.
+    pub systems: Vec<(String, String)>,
+}
+
+/// Parses `<system_id>.volumes = projects:/mnt/projects, data`; a missing
+/// guest path defaults to `/mnt/<name>`.
+pub fn parse_volume_mounts(value: &str) -> Vec<VolumeMount> {
+    value
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| match entry.split_once(':') {
+            Some((name, guest)) if !guest.is_empty() => VolumeMount {
+                name: name.to_string(),
+                guest_path: guest.to_string(),
+            },
+            _ => VolumeMount {
+                name: entry.trim_end_matches(':').to_string(),
+                guest_path: format!("/mnt/{}", entry.trim_end_matches(':')),
+            },
+        })
+        .collect()
+}
+
+pub fn expand_home(path: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    match path.strip_prefix("~/") {
+        Some(rest) => Ok(get_home_dir()?.join(rest)),
+        None if path == "~" => get_home_dir(),
+        None => Ok(PathBuf::from(path)),
+    }
+}
+
+pub fn get_system_volumes(config_manager: &ConfigManager, system_id: &str) -> Result<Vec<VolumeMount>, Box<dyn std::error::Error>> {
+    let value = config_manager.get_system_setting(system_id, "volumes")?.unwrap_or_default();
+    Ok(parse_volume_mounts(&value))
+}
+
+/// Host→guest binds for the volumes attached to the system, creating the
+/// host directories on first use.
+pub fn resolve_volume_binds(config_manager: &ConfigManager, system_id: &str) -> Result<Vec<(String, String)>, Box<dyn std::error::Error>> {
+    let definitions = config_manager.get_volume_definitions()?;
+    let mut binds = Vec::new();
+
+    for mount in get_system_volumes(config_manager, system_id)? {
+        let (_, host) = definitions
+            .iter()
+            .find(|(name, _)| *name == mount.name)
+            .ok_or_else(|| format!("未定义的共享卷: {} (请在配置中设置 volume.{})", mount.name, mount.name))?;
+
+        let host_path = expand_home(host)?;
+        fs::create_dir_all(&host_path)?;
+        binds.push((host_path.to_string_lossy().to_string(), mount.guest_path));
+    }
+
+    Ok(binds)
+}
+
+pub fn list_volumes(config_manager: &ConfigManager) -> Result<Vec<VolumeInfo>, Box<dyn std::error::Error>> {
+    let mut systems = get_installed_systems()?;
+    systems.sort();
+
+    let mut attachments = Vec::new();
+    for system_id in &systems {
+        for mount in get_system_volumes(config_manager, system_id)? {
+            attachments.push((system_id.clone(), mount));
+        }
+    }
+
+    config_manager
+        .get_volume_definitions()?
+        .into_iter()
+        .map(|(name, host)| {
+            let systems = attachments
+                .iter()
+                .filter(|(_, mount)| mount.name == name)
+                .map(|(system_id, mount)| (system_id.clone(), mount.guest_path.clone()))
+                .collect();
+            Ok(VolumeInfo { host_path: expand_home(&host)?, name, systems })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_volume_mounts() {
+        assert_eq!(parse_volume_mounts("projects:/work, data"), vec![
+            VolumeMount { name: "projects".to_string(), guest_path: "/work".to_string() },
+            VolumeMount { name: "data".to_string(), guest_path: "/mnt/data".to_string() },
+        ]);
+        assert!(parse_volume_mounts("").is_empty());
+    }
+
+    #[test]
+    fn test_resolve_volume_binds_creates_host_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let host = temp_dir.path().join("volumes/projects");
+        fs::write(
+            temp_dir.path().join("config"),
+            format!("volume.projects = {}\ndebian1.volumes = projects:/work\narch1.volumes = missing\n", host.display()),
+        ).unwrap();
+        let config_manager = ConfigManager::with_dir(temp_dir.path());
+
+        let binds = resolve_volume_binds(&config_manager, "debian1").unwrap();
+        assert_eq!(binds, vec![(host.to_string_lossy().to_string(), "/work".to_string())]);
+        assert!(host.is_dir());
+
+        assert!(resolve_volume_binds(&config_manager, "arch1").is_err());
+        assert!(resolve_volume_binds(&config_manager, "fedora1").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_expand_home() {
+        let home = get_home_dir().unwrap();
+        assert_eq!(expand_home("~/termos/.volumes/a").unwrap(), home.join("termos/.volumes/a"));
+        assert_eq!(expand_home("/sdcard").unwrap(), PathBuf::from("/sdcard"));
+    }
+}
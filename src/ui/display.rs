@@ -1,5 +1,13 @@
-use crate::distro::SystemMeta;
+use crate::distro::{DistroDefinition, SystemMeta};
+use crate::config::ConfigManager;
+use crate::config::mirror_bench::MirrorBench;
+use crate::system::check::{CheckItem, Problem};
+use crate::system::disk_usage::total_disk_usage;
+use crate::system::info::SystemInfo;
+use crate::system::session::Session;
+use crate::system::volumes::{get_system_volumes, VolumeInfo};
 use crate::i18n::Translator;
+use crate::utils::arch::Architecture;
 use crate::ui::colors::Theme;
 
 #[allow(dead_code)]
@@ -10,34 +18,306 @@ pub fn get_terminal_width() -> usize {
     }
 }
 
-pub fn display_system_list(metas: &[(String, SystemMeta)], translator: &Translator) -> Result<(), Box<dyn std::error::Error>> {
-    if metas.is_empty() {
+/// `invalid` holds the IDs and load errors of systems whose metadata could
+/// not be read; they are listed with a marker rather than left out.
+pub fn display_system_list(metas: &[(String, SystemMeta)], invalid: &[(String, String)], translator: &Translator) -> Result<(), Box<dyn std::error::Error>> {
+    if metas.is_empty() && invalid.is_empty() {
         println!("\n[ {} ]\n", translator.t("no_systems_here"));
         return Ok(());
     }
     
     println!("\n{}\n", translator.t("installed_systems_header"));
+    let config_manager = ConfigManager::new().ok();
     
     for (system_id, meta) in metas {
         println!("  {}  ", meta.name);
         println!("  ──────────────────────────────────");
         
         let unknown_date = translator.t("unknown_date");
-        let date = meta.created_at.split('T').next().unwrap_or(&unknown_date);
+        let date = meta.created_at.as_deref().and_then(|created| created.split('T').next()).unwrap_or(&unknown_date);
         let system_id_label = translator.t("system_id_label");
         let created_at_label = translator.t("created_at_label");
-        let user_group_label = translator.t("user_group_label");
-        let permissions_label = translator.t("permissions_label");
         println!("  {}: {}    {}: {}", system_id_label, system_id, created_at_label, date);
-        println!("  {}: {}    {}: {}", user_group_label, meta.user_group, permissions_label, meta.permissions);
-        println!("  {}: {}    {}: {}", translator.t("user_group_label"), meta.user_group, translator.t("permissions_label"), meta.permissions);
+        let arch = if meta.emulated {
+            format!("{} ({})", meta.arch.to_str(), translator.t("emulated_label"))
+        } else {
+            meta.arch.to_str().to_string()
+        };
+        println!("  {}: {}    {}: {}    {}: {}",
+            translator.t("distro_label"), meta.distro,
+            translator.t("arch_label"), arch,
+            translator.t("backend_label"), meta.backend.as_str());
+        
+        if let Some(usage) = &meta.disk_usage {
+            let scanned = usage.scanned_at.split('T').next().unwrap_or(&usage.scanned_at);
+            println!("  {}: {}", translator.t("size_label"), translator.t_fmt("size_value", &[
+                &format_size(usage.apparent_bytes),
+                &format_size(usage.disk_bytes),
+                scanned,
+            ]));
+        }
+        
+        let volumes = config_manager
+            .as_ref()
+            .and_then(|config| get_system_volumes(config, system_id).ok())
+            .unwrap_or_default();
+        if !volumes.is_empty() {
+            let mounts: Vec<String> = volumes.iter().map(|v| format!("{} → {}", v.name, v.guest_path)).collect();
+            println!("  {}: {}", translator.t("volumes_label"), mounts.join(", "));
+        }
         
         println!();
     }
     
+    for (system_id, error) in invalid {
+        println!("  {}  [{}]", system_id, translator.t("invalid_metadata_label"));
+        println!("  ──────────────────────────────────");
+        println!("  {}\n", error);
+    }
+    
+    let (apparent, disk) = total_disk_usage(metas);
+    println!("  {}\n", translator.t_fmt("total_size", &[&format_size(apparent), &format_size(disk)]));
+    
     Ok(())
 }
 
+/// Human-readable size in binary units, e.g. `1.5 GiB`.
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+pub fn display_system_info(info: &SystemInfo, translator: &Translator) {
+    let meta = &info.meta;
+    let unknown = translator.t("unknown_value");
+    let row = |key: &str, value: &str| println!("  {}: {}", translator.t(key), value);
+    
+    println!("\n  {}  ", meta.name);
+    println!("  ──────────────────────────────────");
+    row("system_id_label", &info.id);
+    match &info.pretty_name {
+        Some(pretty) => row("distro_label", &format!("{} ({})", meta.distro, pretty)),
+        None => row("distro_label", &meta.distro),
+    }
+    row("release_label", info.version_id.as_deref().or(meta.release.as_deref()).unwrap_or(&unknown));
+    if meta.emulated {
+        row("arch_label", &format!("{} ({})", meta.arch.to_str(), translator.t("emulated_label")));
+    } else {
+        row("arch_label", meta.arch.to_str());
+    }
+    row("backend_label", meta.backend.as_str());
+    row("install_mode_label", meta.install_mode.as_str());
+    row("default_user_label", &meta.default_user);
+    row("created_at_label", meta.created_at.as_deref().unwrap_or(&unknown));
+    row("last_login_label", meta.last_login.as_deref().unwrap_or(&translator.t("never_logged_in")));
+    if let Some(usage) = &meta.disk_usage {
+        row("size_label", &translator.t_fmt("size_value", &[
+            &format_size(usage.apparent_bytes),
+            &format_size(usage.disk_bytes),
+            &usage.scanned_at,
+        ]));
+    }
+    match (info.package_count, info.package_manager) {
+        (Some(count), Some(manager)) => row("packages_label", &format!("{} ({})", count, manager.as_str())),
+        (None, Some(manager)) => row("packages_label", &format!("{} ({})", unknown, manager.as_str())),
+        _ => row("packages_label", &unknown),
+    }
+    if info.sessions > 0 {
+        row("status_label", &translator.t_fmt("status_running", &[&info.sessions.to_string()]));
+    } else {
+        row("status_label", &translator.t("status_stopped"));
+    }
+    if !info.services.is_empty() {
+        row("services_label", &info.services.join(", "));
+    }
+    if let Some(url) = &meta.source_url {
+        row("source_label", url);
+    }
+    if let Some(checksum) = &meta.checksum {
+        row("checksum_label", checksum);
+    }
+    if let Some(desktop) = &meta.desktop {
+        row("desktop_label", desktop);
+    }
+    if let Some(port) = meta.ssh_port {
+        row("ssh_port_label", &port.to_string());
+    }
+    row("path_label", &info.path.display().to_string());
+    row("launcher_label", &info.launcher.display().to_string());
+    
+    if !info.binds.is_empty() {
+        println!("  {}:", translator.t("binds_label"));
+        for (host, guest) in &info.binds {
+            println!("    {} → {}", host, guest);
+        }
+    }
+    println!("  {}:", translator.t("env_label"));
+    for (name, value) in &info.env {
+        println!("    {}={}", name, value);
+    }
+    println!();
+}
+
+fn problem_message(problem: &Problem, translator: &Translator) -> String {
+    match problem {
+        Problem::Missing => translator.t("problem_missing"),
+        Problem::NotExecutable => translator.t("problem_not_executable"),
+        Problem::Mode { expected, actual } => translator.t_fmt("problem_mode", &[&format!("{:o}", expected), &format!("{:o}", actual)]),
+        Problem::Invalid(reason) => translator.t_fmt("problem_invalid", &[reason]),
+        Problem::Outdated => translator.t("problem_outdated"),
+        Problem::NoNameserver => translator.t("problem_no_nameserver"),
+        Problem::NoLocalhost => translator.t("problem_no_localhost"),
+    }
+}
+
+pub fn display_check_results(items: &[CheckItem], translator: &Translator) {
+    println!();
+    for item in items {
+        match &item.problem {
+            None => println!("  ✓ {}", item.subject),
+            Some(problem) if item.is_repairable() => println!(
+                "  ✗ {}: {} ({})",
+                item.subject,
+                problem_message(problem, translator),
+                translator.t("check_repairable")
+            ),
+            Some(problem) => println!("  ✗ {}: {}", item.subject, problem_message(problem, translator)),
+        }
+    }
+    println!();
+}
+
+pub fn display_sessions(sessions: &[(String, Session)], translator: &Translator) {
+    if sessions.is_empty() {
+        println!("\n[ {} ]\n", translator.t("no_active_sessions"));
+        return;
+    }
+    
+    println!("\n{}\n", translator.t("active_sessions_header"));
+    println!(
+        "  {:<16} {:>8}  {:<20}  {}",
+        translator.t("system_id_label"),
+        translator.t("session_pid_label"),
+        translator.t("session_started_label"),
+        translator.t("session_command_label")
+    );
+    
+    for (system_id, session) in sessions {
+        println!("  {:<16} {:>8}  {:<20}  {}", system_id, session.pid, session.started_at, session.command);
+    }
+    println!();
+}
+
+pub fn display_services(services: &[(String, Option<Session>)], translator: &Translator) {
+    if services.is_empty() {
+        println!("\n[ {} ]\n", translator.t("no_services_configured"));
+        return;
+    }
+    
+    println!();
+    for (name, status) in services {
+        match status {
+            Some(session) => println!(
+                "  {:<16} {}",
+                name,
+                translator.t_fmt("service_running_since", &[&session.pid.to_string(), &session.started_at])
+            ),
+            None => println!("  {:<16} {}", name, translator.t("service_stopped_status")),
+        }
+    }
+    println!();
+}
+
+pub fn display_volumes(volumes: &[VolumeInfo], translator: &Translator) {
+    if volumes.is_empty() {
+        println!("\n[ {} ]\n", translator.t("no_volumes_defined"));
+        return;
+    }
+    
+    println!("\n{}\n", translator.t("volumes_header"));
+    for volume in volumes {
+        println!("  {}  {}", volume.name, volume.host_path.display());
+        if volume.systems.is_empty() {
+            println!("    {}", translator.t("volume_not_attached"));
+        }
+        for (system_id, guest_path) in &volume.systems {
+            println!("    {} → {}", system_id, guest_path);
+        }
+    }
+    println!();
+}
+
+/// Catalog entries with their architectures and the download size for
+/// `size_arch`, as listed by `search` and `available`.
+pub fn display_available_distros(distros: &[DistroDefinition], sizes: &[Option<u64>], size_arch: Option<&Architecture>, translator: &Translator) {
+    let size_label = match size_arch {
+        Some(arch) => format!("{} ({})", translator.t("size_label"), arch.to_str()),
+        None => translator.t("size_label"),
+    };
+    println!(
+        "\n  {:<12} {:<28} {:<8} {:<32} {}",
+        translator.t("distro_label"),
+        translator.t("name_label"),
+        translator.t("family_label"),
+        translator.t("arch_label"),
+        size_label
+    );
+    
+    for (distro, size) in distros.iter().zip(sizes) {
+        let archs: Vec<&str> = Architecture::all().iter().filter(|arch| distro.supports_arch(arch)).map(|arch| arch.to_str()).collect();
+        println!(
+            "  {:<12} {:<28} {:<8} {:<32} {}",
+            distro.name.as_str(),
+            distro.label(),
+            distro.package_manager.map(|manager| manager.as_str()).unwrap_or("-"),
+            archs.join(","),
+            size.map(format_size).unwrap_or_else(|| "-".to_string())
+        );
+    }
+    println!();
+}
+
+pub fn display_mirror_bench(results: &[MirrorBench], translator: &Translator) {
+    println!(
+        "\n  {:>2}  {:>10}  {:>12}  {}",
+        "#",
+        translator.t("mirror_bench_latency_label"),
+        translator.t("mirror_bench_speed_label"),
+        translator.t("mirror_label")
+    );
+    
+    for (rank, result) in results.iter().enumerate() {
+        match &result.probe {
+            Ok(probe) => println!(
+                "  {:>2}  {:>7} ms  {:>10}/s  {}",
+                rank + 1,
+                probe.latency.as_millis(),
+                format_size(probe.bytes_per_sec as u64),
+                result.mirror
+            ),
+            Err(reason) => println!(
+                "  {:>2}  {:>10}  {:>12}  {} ({})",
+                "-",
+                "-",
+                "-",
+                result.mirror,
+                translator.t_fmt("mirror_bench_failed", &[reason])
+            ),
+        }
+    }
+    println!();
+}
+
 pub fn print_section(title: &str) {
     println!("\n{}", title);
     println!("{}", "─".repeat(title.len()));
@@ -73,3 +353,15 @@ pub fn print_info_theme(message: &str, theme: &Theme) {
     println!("  {}", theme.info(&format!("ℹ {}", message)));
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3.0 GiB");
+    }
+}
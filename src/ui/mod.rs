@@ -4,6 +4,10 @@ pub mod progress_base;
 pub mod progress_download;
 pub mod progress_extraction;
 pub mod progress;
+pub mod output;
+pub mod output_json;
+pub mod output_records;
+pub mod output_tsv;
 
 pub use display::*;
 pub use progress_base::ProgressBar;
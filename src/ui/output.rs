@@ -0,0 +1,150 @@
+pub use super::output_json::{render_json, render_json_object};
+pub use super::output_records::*;
+pub use super::output_tsv::render_tsv;
+
+/// `--output` format of commands that support scripting. The machine
+/// formats ignore the language and theme and print no decoration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    #[default]
+    Human,
+    Json,
+    Tsv,
+}
+
+impl OutputFormat {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "human" | "text" => Some(OutputFormat::Human),
+            "json" => Some(OutputFormat::Json),
+            "tsv" => Some(OutputFormat::Tsv),
+            _ => None,
+        }
+    }
+
+    pub fn is_machine(&self) -> bool {
+        *self != OutputFormat::Human
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Field {
+    Str(String),
+    Int(u64),
+    Bool(bool),
+    /// JSON array; joined with `,` in TSV.
+    List(Vec<String>),
+    Null,
+}
+
+impl From<&str> for Field {
+    fn from(value: &str) -> Self {
+        Field::Str(value.to_string())
+    }
+}
+
+impl From<String> for Field {
+    fn from(value: String) -> Self {
+        Field::Str(value)
+    }
+}
+
+impl<T: Into<Field>> From<Option<T>> for Field {
+    fn from(value: Option<T>) -> Self {
+        value.map(Into::into).unwrap_or(Field::Null)
+    }
+}
+
+impl From<u64> for Field {
+    fn from(value: u64) -> Self {
+        Field::Int(value)
+    }
+}
+
+impl From<u32> for Field {
+    fn from(value: u32) -> Self {
+        Field::Int(value as u64)
+    }
+}
+
+impl From<u16> for Field {
+    fn from(value: u16) -> Self {
+        Field::Int(value as u64)
+    }
+}
+
+impl From<usize> for Field {
+    fn from(value: usize) -> Self {
+        Field::Int(value as u64)
+    }
+}
+
+impl From<bool> for Field {
+    fn from(value: bool) -> Self {
+        Field::Bool(value)
+    }
+}
+
+/// One object (JSON) or row (TSV). Keys keep their insertion order.
+pub type Record = Vec<(&'static str, Field)>;
+
+/// Removes `--output <fmt>` / `--output=<fmt>` from `args`.
+pub fn take_output_format(args: &[String]) -> Result<(OutputFormat, Vec<String>), String> {
+    let mut format = OutputFormat::Human;
+    let mut rest = Vec::new();
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        let value = if arg == "--output" {
+            iter.next().ok_or_else(|| "--output".to_string())?.as_str()
+        } else if let Some(value) = arg.strip_prefix("--output=") {
+            value
+        } else {
+            rest.push(arg.clone());
+            continue;
+        };
+        format = OutputFormat::from_str(value).ok_or_else(|| value.to_string())?;
+    }
+
+    Ok((format, rest))
+}
+
+/// Prints records in a machine format; `header` lists the TSV columns so an
+/// empty result still has one.
+pub fn print_records(format: OutputFormat, header: &[&str], records: &[Record]) {
+    match format {
+        OutputFormat::Json => println!("{}", render_json(records)),
+        OutputFormat::Tsv => print!("{}", render_tsv(header, records)),
+        OutputFormat::Human => {}
+    }
+}
+
+/// Like [`print_records`] for commands about a single item: JSON output is
+/// one object instead of an array.
+pub fn print_record(format: OutputFormat, header: &[&str], record: &Record) {
+    match format {
+        OutputFormat::Json => println!("{}", render_json_object(record)),
+        _ => print_records(format, header, std::slice::from_ref(record)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_take_output_format() {
+        assert_eq!(
+            take_output_format(&strings(&["--output", "json", "--refresh"])).unwrap(),
+            (OutputFormat::Json, strings(&["--refresh"]))
+        );
+        assert_eq!(take_output_format(&strings(&["--output=tsv"])).unwrap().0, OutputFormat::Tsv);
+        assert_eq!(take_output_format(&[]).unwrap().0, OutputFormat::Human);
+        assert_eq!(take_output_format(&strings(&["--output", "yaml"])), Err("yaml".to_string()));
+        assert!(take_output_format(&strings(&["--output"])).is_err());
+    }
+}
@@ -0,0 +1,70 @@
+use crate::ui::output::{Field, Record};
+
+fn json_escape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len() + 2);
+    result.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+fn json_field(field: &Field) -> String {
+    match field {
+        Field::Str(value) => json_escape(value),
+        Field::Int(value) => value.to_string(),
+        Field::Bool(value) => value.to_string(),
+        Field::List(values) => {
+            let items: Vec<String> = values.iter().map(|value| json_escape(value)).collect();
+            format!("[{}]", items.join(","))
+        }
+        Field::Null => "null".to_string(),
+    }
+}
+
+pub fn render_json_object(record: &Record) -> String {
+    let fields: Vec<String> = record
+        .iter()
+        .map(|(key, field)| format!("{}:{}", json_escape(key), json_field(field)))
+        .collect();
+    format!("{{{}}}", fields.join(","))
+}
+
+pub fn render_json(records: &[Record]) -> String {
+    let objects: Vec<String> = records.iter().map(render_json_object).collect();
+    format!("[{}]", objects.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_json() {
+        let records = vec![vec![
+            ("id", "debian1".into()),
+            ("command", "echo \"a\tb\"\n".into()),
+            ("pid", 42u32.into()),
+            ("emulated", false.into()),
+            ("release", Option::<String>::None.into()),
+        ]];
+        assert_eq!(
+            render_json(&records),
+            r#"[{"id":"debian1","command":"echo \"a\tb\"\n","pid":42,"emulated":false,"release":null}]"#
+        );
+        assert_eq!(render_json(&[]), "[]");
+        assert_eq!(
+            render_json_object(&vec![("binds", Field::List(vec!["/a:/b".to_string()]))]),
+            r#"{"binds":["/a:/b"]}"#
+        );
+    }
+}
@@ -0,0 +1,77 @@
+use crate::distro::SystemMeta;
+use crate::system::info::SystemInfo;
+use crate::system::session::Session;
+use crate::ui::output::{Field, Record};
+
+pub const SYSTEM_FIELDS: [&str; 12] = [
+    "id", "name", "distro", "release", "arch", "emulated", "backend",
+    "install_mode", "created_at", "last_login", "apparent_bytes", "disk_bytes",
+];
+
+pub fn system_record(system_id: &str, meta: &SystemMeta) -> Record {
+    let usage = meta.disk_usage.as_ref();
+    vec![
+        ("id", system_id.into()),
+        ("name", meta.name.as_str().into()),
+        ("distro", meta.distro.as_str().into()),
+        ("release", meta.release.clone().into()),
+        ("arch", meta.arch.to_str().into()),
+        ("emulated", meta.emulated.into()),
+        ("backend", meta.backend.as_str().into()),
+        ("install_mode", meta.install_mode.as_str().into()),
+        ("created_at", meta.created_at.clone().into()),
+        ("last_login", meta.last_login.clone().into()),
+        ("apparent_bytes", usage.map(|u| u.apparent_bytes).into()),
+        ("disk_bytes", usage.map(|u| u.disk_bytes).into()),
+    ]
+}
+
+pub const SESSION_FIELDS: [&str; 4] = ["system", "pid", "started_at", "command"];
+
+pub fn session_record(system_id: &str, session: &Session) -> Record {
+    vec![
+        ("system", system_id.into()),
+        ("pid", session.pid.into()),
+        ("started_at", session.started_at.as_str().into()),
+        ("command", session.command.as_str().into()),
+    ]
+}
+
+pub const INFO_FIELDS: [&str; 27] = [
+    "id", "name", "distro", "release", "arch", "emulated", "backend",
+    "install_mode", "created_at", "last_login", "apparent_bytes", "disk_bytes",
+    "default_user", "source_url", "checksum", "download_size", "desktop", "ssh_port",
+    "os_release", "version_id", "package_manager", "package_count",
+    "binds", "env", "launcher", "sessions", "services",
+];
+
+/// Everything in [`system_record`] plus the live details; `binds` entries
+/// are `host:guest` and `env` entries `NAME=value`.
+pub fn info_record(info: &SystemInfo) -> Record {
+    let meta = &info.meta;
+    let mut record = system_record(&info.id, meta);
+    record.extend([
+        ("default_user", meta.default_user.as_str().into()),
+        ("source_url", meta.source_url.clone().into()),
+        ("checksum", meta.checksum.clone().into()),
+        ("download_size", meta.download_size.into()),
+        ("desktop", meta.desktop.clone().into()),
+        ("ssh_port", meta.ssh_port.into()),
+        ("os_release", info.pretty_name.clone().into()),
+        ("version_id", info.version_id.clone().into()),
+        ("package_manager", info.package_manager.map(|m| m.as_str()).into()),
+        ("package_count", info.package_count.into()),
+        ("binds", Field::List(info.binds.iter().map(|(host, guest)| format!("{}:{}", host, guest)).collect())),
+        ("env", Field::List(info.env.iter().map(|(name, value)| format!("{}={}", name, value)).collect())),
+        ("launcher", info.launcher.to_string_lossy().to_string().into()),
+        ("sessions", info.sessions.into()),
+        ("services", Field::List(info.services.clone())),
+    ]);
+    record
+}
+
+pub const CONFIG_FIELDS: [&str; 2] = ["key", "value"];
+
+pub fn config_record(key: &str, value: Option<String>) -> Record {
+    vec![("key", key.into()), ("value", value.into())]
+}
@@ -0,0 +1,36 @@
+use crate::ui::output::{Field, Record};
+
+fn tsv_field(field: &Field) -> String {
+    match field {
+        Field::Str(value) => value.replace('\\', "\\\\").replace('\t', "\\t").replace('\n', "\\n").replace('\r', "\\r"),
+        Field::Int(value) => value.to_string(),
+        Field::Bool(value) => value.to_string(),
+        Field::List(values) => tsv_field(&Field::Str(values.join(","))),
+        Field::Null => String::new(),
+    }
+}
+
+/// Header line with the keys of `header`, then one line per record. Tabs,
+/// newlines and backslashes in values are escaped as `\t`, `\n` and `\\`.
+pub fn render_tsv(header: &[&str], records: &[Record]) -> String {
+    let mut result = header.join("\t");
+    result.push('\n');
+    for record in records {
+        let fields: Vec<String> = record.iter().map(|(_, field)| tsv_field(field)).collect();
+        result.push_str(&fields.join("\t"));
+        result.push('\n');
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::output::{config_record, CONFIG_FIELDS};
+
+    #[test]
+    fn test_render_tsv() {
+        let records = vec![config_record("shell", Some("/bin/zsh\t--login".to_string())), config_record("timezone", None)];
+        assert_eq!(render_tsv(&CONFIG_FIELDS, &records), "key\tvalue\nshell\t/bin/zsh\\t--login\ntimezone\t\n");
+    }
+}
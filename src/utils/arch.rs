@@ -21,6 +21,35 @@ impl Architecture {
         }
     }
     
+    pub fn all() -> [Architecture; 5] {
+        [
+            Architecture::Aarch64,
+            Architecture::Arm,
+            Architecture::X86_64,
+            Architecture::I686,
+            Architecture::Riscv64,
+        ]
+    }
+    
+    pub fn qemu_binary(&self) -> &'static str {
+        match self {
+            Architecture::Aarch64 => "qemu-aarch64",
+            Architecture::Arm => "qemu-arm",
+            Architecture::X86_64 => "qemu-x86_64",
+            Architecture::I686 => "qemu-i386",
+            Architecture::Riscv64 => "qemu-riscv64",
+        }
+    }
+    
+    /// 64-bit hosts can usually execute their 32-bit counterpart without emulation.
+    pub fn can_run_natively(&self, target: &Architecture) -> bool {
+        self == target
+            || matches!(
+                (self, target),
+                (Architecture::X86_64, Architecture::I686) | (Architecture::Aarch64, Architecture::Arm)
+            )
+    }
+    
     pub fn from_str(s: &str) -> Option<Self> {
         match s.to_lowercase().as_str() {
             "aarch64" | "arm64" => Some(Architecture::Aarch64),
@@ -33,6 +62,31 @@ impl Architecture {
     }
 }
 
+/// Architecture of an ELF binary, read from its `e_machine` header field.
+pub fn elf_architecture(path: &std::path::Path) -> Option<Architecture> {
+    use std::io::Read;
+
+    let mut header = [0u8; 20];
+    std::fs::File::open(path).ok()?.read_exact(&mut header).ok()?;
+    if header[..4] != [0x7f, b'E', b'L', b'F'] {
+        return None;
+    }
+
+    let machine = match header[5] {
+        1 => u16::from_le_bytes([header[18], header[19]]),
+        2 => u16::from_be_bytes([header[18], header[19]]),
+        _ => return None,
+    };
+    match machine {
+        0x03 => Some(Architecture::I686),
+        0x28 => Some(Architecture::Arm),
+        0x3e => Some(Architecture::X86_64),
+        0xb7 => Some(Architecture::Aarch64),
+        0xf3 => Some(Architecture::Riscv64),
+        _ => None,
+    }
+}
+
 pub fn detect_architecture() -> Result<Architecture, Box<dyn std::error::Error>> {
     let output = Command::new("uname")
         .arg("-m")
@@ -50,6 +104,22 @@ pub fn detect_architecture() -> Result<Architecture, Box<dyn std::error::Error>>
         .ok_or_else(|| format!("Unsupported architecture: {}", arch_str).into())
 }
 
+pub fn find_qemu_binary(arch: &Architecture) -> Option<String> {
+    crate::utils::cmd::run_command_with_output(&format!("command -v {}", arch.qemu_binary()))
+        .ok()
+        .map(|path| path.trim().to_string())
+        .filter(|path| !path.is_empty())
+}
+
+/// Foreign architectures that have a usable `qemu-<arch>` on this host.
+pub fn get_emulated_architectures(host: &Architecture) -> Vec<Architecture> {
+    Architecture::all()
+        .into_iter()
+        .filter(|arch| !host.can_run_natively(arch))
+        .filter(|arch| find_qemu_binary(arch).is_some())
+        .collect()
+}
+
 pub fn prompt_manual_selection(translator: &Translator) -> Result<Architecture, Box<dyn std::error::Error>> {
     println!("{}", translator.t("arch_detection_prompt"));
     println!("{}", translator.t("arch_option_1"));
@@ -113,4 +183,42 @@ mod tests {
         assert_eq!(Architecture::from_str("riscv64"), Some(Architecture::Riscv64));
         assert_eq!(Architecture::from_str("unknown"), None);
     }
+
+    #[test]
+    fn test_architecture_qemu_binary() {
+        assert_eq!(Architecture::X86_64.qemu_binary(), "qemu-x86_64");
+        assert_eq!(Architecture::I686.qemu_binary(), "qemu-i386");
+        assert_eq!(Architecture::Riscv64.qemu_binary(), "qemu-riscv64");
+    }
+
+    #[test]
+    fn test_architecture_can_run_natively() {
+        assert!(Architecture::Aarch64.can_run_natively(&Architecture::Aarch64));
+        assert!(Architecture::Aarch64.can_run_natively(&Architecture::Arm));
+        assert!(Architecture::X86_64.can_run_natively(&Architecture::I686));
+        assert!(!Architecture::Aarch64.can_run_natively(&Architecture::X86_64));
+        assert!(!Architecture::Arm.can_run_natively(&Architecture::Aarch64));
+    }
+
+    #[test]
+    fn test_emulated_architectures_have_qemu() {
+        assert!(get_emulated_architectures(&Architecture::Aarch64)
+            .iter()
+            .all(|arch| find_qemu_binary(arch).is_some()));
+    }
+
+    #[test]
+    fn test_elf_architecture() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("sh");
+
+        let mut header = vec![0x7f, b'E', b'L', b'F', 2, 1, 1, 0];
+        header.resize(18, 0);
+        header.extend_from_slice(&0xb7u16.to_le_bytes());
+        std::fs::write(&path, &header).unwrap();
+        assert_eq!(elf_architecture(&path), Some(Architecture::Aarch64));
+
+        std::fs::write(&path, "#!/bin/sh\necho not an elf binary\n").unwrap();
+        assert_eq!(elf_architecture(&path), None);
+    }
 }
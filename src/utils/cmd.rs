@@ -57,6 +57,10 @@ pub fn check_command_exists(command: &str) -> bool {
         .unwrap_or(false)
 }
 
+pub fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -101,4 +105,10 @@ mod tests {
         let result = check_command_exists("nonexistentcommand12345");
         assert!(!result);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("plain"), "'plain'");
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+    }
+}
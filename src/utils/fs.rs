@@ -86,4 +86,21 @@ mod tests {
         let config = result.unwrap();
         assert!(config.is_empty());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_resolve_guest_path_stays_in_rootfs() {
+        let temp_dir = TempDir::new().unwrap();
+        let rootfs = temp_dir.path();
+        std::fs::create_dir_all(rootfs.join("usr/bin")).unwrap();
+        std::fs::write(rootfs.join("usr/bin/busybox"), "").unwrap();
+        std::os::unix::fs::symlink("usr/bin", rootfs.join("bin")).unwrap();
+        std::os::unix::fs::symlink("/bin/busybox", rootfs.join("usr/bin/sh")).unwrap();
+        std::os::unix::fs::symlink("../../etc/loop", rootfs.join("usr/bin/loop")).unwrap();
+        std::os::unix::fs::symlink("/usr/bin/loop", rootfs.join("etc")).unwrap();
+
+        assert_eq!(resolve_guest_path(rootfs, "/bin/sh"), Some(rootfs.join("usr/bin/busybox")));
+        assert_eq!(resolve_guest_path(rootfs, "/bin/../bin/busybox"), Some(rootfs.join("usr/bin/busybox")));
+        assert_eq!(resolve_guest_path(rootfs, "/bin/bash"), None);
+        assert_eq!(resolve_guest_path(rootfs, "/etc/os-release"), None);
+    }
+}
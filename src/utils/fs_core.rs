@@ -1,8 +1,9 @@
 use std::env;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::collections::HashMap;
 use crate::distro::SystemMeta;
+use crate::distro::meta::{LEGACY_META_FILE, META_FILE};
 use std::process::{Command, Stdio};
 use std::io::{BufRead, BufReader};
 
@@ -13,6 +14,77 @@ pub fn get_home_dir() -> Result<PathBuf, Box<dyn std::error::Error>> {
     }
 }
 
+pub fn get_system_dir(system_id: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    Ok(get_home_dir()?.join("termos").join(system_id))
+}
+
+/// Reads `meta.toml`, migrating a legacy `meta.txt` first if that is all
+/// the system has. The old file is kept as `meta.txt.bak`.
+pub fn load_system_meta(system_id: &str) -> Result<SystemMeta, Box<dyn std::error::Error>> {
+    let system_dir = get_system_dir(system_id)?;
+    let meta_path = system_dir.join(META_FILE);
+    let legacy_path = system_dir.join(LEGACY_META_FILE);
+
+    if !meta_path.exists() && legacy_path.exists() {
+        let content = fs::read_to_string(&legacy_path)?;
+        let host_arch = crate::utils::arch::detect_architecture()?;
+        let meta = SystemMeta::from_legacy(&content, system_id, host_arch)
+            .map_err(|e| format!("无法迁移 {}: {}", legacy_path.display(), e))?;
+        save_system_meta(system_id, &meta)?;
+        fs::rename(&legacy_path, legacy_path.with_extension("txt.bak"))?;
+        return Ok(meta);
+    }
+
+    let content = fs::read_to_string(&meta_path)
+        .map_err(|e| format!("无法读取 {}: {}", meta_path.display(), e))?;
+    SystemMeta::from_toml(&content).map_err(|e| format!("{}: {}", meta_path.display(), e).into())
+}
+
+pub fn save_system_meta(system_id: &str, meta: &SystemMeta) -> Result<(), Box<dyn std::error::Error>> {
+    fs::write(get_system_dir(system_id)?.join(META_FILE), meta.to_toml())?;
+    Ok(())
+}
+
+/// Host path of `guest_path` inside `rootfs`, following symlinks the way the
+/// guest sees them: absolute targets stay inside the rootfs. `None` if the
+/// path does not exist or the links loop.
+pub fn resolve_guest_path(rootfs: &Path, guest_path: &str) -> Option<PathBuf> {
+    let mut pending: Vec<String> = guest_path.split('/').rev().map(String::from).collect();
+    let mut resolved = PathBuf::new();
+    let mut hops = 0;
+
+    while let Some(component) = pending.pop() {
+        match component.as_str() {
+            "" | "." => continue,
+            ".." => {
+                resolved.pop();
+                continue;
+            }
+            _ => {}
+        }
+
+        let candidate = resolved.join(&component);
+        let host_path = rootfs.join(&candidate);
+        match fs::read_link(&host_path) {
+            Ok(target) => {
+                hops += 1;
+                if hops > 40 {
+                    return None;
+                }
+                if target.is_absolute() {
+                    resolved = PathBuf::new();
+                }
+                let target = target.to_string_lossy().to_string();
+                pending.extend(target.split('/').rev().map(String::from));
+            }
+            Err(_) if fs::symlink_metadata(&host_path).is_ok() => resolved = candidate,
+            Err(_) => return None,
+        }
+    }
+
+    Some(rootfs.join(resolved))
+}
+
 pub fn get_installed_systems() -> Result<Vec<String>, Box<dyn std::error::Error>> {
     let home = get_home_dir()?;
     let termos_dir = home.join("termos");
@@ -31,7 +103,10 @@ pub fn get_installed_systems() -> Result<Vec<String>, Box<dyn std::error::Error>
         if path.is_dir() {
             if let Some(name) = path.file_name() {
                 if let Some(name_str) = name.to_str() {
-                    systems.push(name_str.to_string());
+                    // Hidden directories hold shared state such as volumes.
+                    if !name_str.starts_with('.') {
+                        systems.push(name_str.to_string());
+                    }
                 }
             }
         }
@@ -40,23 +115,24 @@ pub fn get_installed_systems() -> Result<Vec<String>, Box<dyn std::error::Error>
     Ok(systems)
 }
 
-pub fn get_system_metas() -> Result<Vec<(String, SystemMeta)>, Box<dyn std::error::Error>> {
+/// Systems with loadable metadata, and the IDs and load errors of the rest.
+pub type SystemMetas = (Vec<(String, SystemMeta)>, Vec<(String, String)>);
+
+/// Metadata of every system, plus the IDs and errors of the systems whose
+/// metadata cannot be loaded.
+pub fn get_system_metas() -> Result<SystemMetas, Box<dyn std::error::Error>> {
     let systems = get_installed_systems()?;
     let mut metas = Vec::new();
+    let mut invalid = Vec::new();
     
     for system_id in systems {
-        let home = get_home_dir()?;
-        let meta_path = home.join("termos").join(&system_id).join("meta.txt");
-        
-        if meta_path.exists() {
-            let content = fs::read_to_string(&meta_path)?;
-            if let Ok(meta) = SystemMeta::from_string(&content) {
-                metas.push((system_id, meta));
-            }
+        match load_system_meta(&system_id) {
+            Ok(meta) => metas.push((system_id, meta)),
+            Err(e) => invalid.push((system_id, e.to_string())),
         }
     }
     
-    Ok(metas)
+    Ok((metas, invalid))
 }
 
 #[allow(dead_code)]
@@ -85,9 +161,13 @@ pub fn read_config_file(path: &PathBuf) -> Result<HashMap<String, String>, Box<d
     Ok(config)
 }
 
+/// `strip_components` leading path components are dropped from every entry,
+/// for archives that wrap the rootfs in a top-level directory. tar detects
+/// the compression, so gzip snapshots unpack as well as xz bundles.
 pub fn extract_tar_xz_with_progress<F>(
     archive_path: &PathBuf,
     extract_dir: &PathBuf,
+    strip_components: u32,
     mut progress_callback: F,
 ) -> Result<u64, Box<dyn std::error::Error>>
 where
@@ -96,7 +176,8 @@ where
     let _total_files = count_files_in_tar_xz(archive_path)?;
     
     let mut child = Command::new("tar")
-        .args(&["-xJf", archive_path.to_str().unwrap(), "-C", extract_dir.to_str().unwrap(), "--verbose"])
+        .args(&["-xf", archive_path.to_str().unwrap(), "-C", extract_dir.to_str().unwrap(), "--verbose"])
+        .arg(format!("--strip-components={}", strip_components))
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .spawn()?;
@@ -138,7 +219,7 @@ pub fn count_files_in_tar_xz(archive_path: &PathBuf) -> Result<u64, Box<dyn std:
 
 pub fn extract_tar_xz(archive_path: &PathBuf, extract_dir: &PathBuf) -> Result<(), Box<dyn std::error::Error>> {
     let status = Command::new("tar")
-        .args(&["-xJf", archive_path.to_str().unwrap(), "-C", extract_dir.to_str().unwrap()])
+        .args(&["-xf", archive_path.to_str().unwrap(), "-C", extract_dir.to_str().unwrap()])
         .status()?;
     
     if !status.success() {
@@ -146,4 +227,15 @@ pub fn extract_tar_xz(archive_path: &PathBuf, extract_dir: &PathBuf) -> Result<(
     }
     
     Ok(())
-}
\ No newline at end of file
+}
+/// `sha256:<hex>` of a file, using the host's `sha256sum`.
+pub fn file_sha256(path: &PathBuf) -> Result<String, Box<dyn std::error::Error>> {
+    let output = Command::new("sha256sum").arg(path).output()?;
+    if !output.status.success() {
+        return Err(format!("无法计算校验和: {}", path.display()).into());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let hex = stdout.split_whitespace().next().ok_or("sha256sum 输出为空")?;
+    Ok(format!("sha256:{}", hex))
+}
@@ -5,6 +5,9 @@ pub mod net_core;
 pub mod net;
 pub mod permissions;
 pub mod arch;
+pub mod process;
+#[cfg(test)]
+pub mod test_http;
 
 pub use fs_core::*;
 pub use net_core::*;
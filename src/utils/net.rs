@@ -3,6 +3,7 @@ pub use super::net_core::*;
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::test_http::{serve, serve_redirect};
     use tempfile::TempDir;
     use std::fs;
 
@@ -25,6 +26,39 @@ mod tests {
         assert!(!content.is_empty());
     }
 
+    #[test]
+    fn test_download_candidates() {
+        let proxied = "https://gh-proxy.org/https://github.com/termux/proot-distro/releases/download/v4.29.0/void-aarch64-pd-v4.29.0.tar.xz";
+        let direct = "https://github.com/termux/proot-distro/releases/download/v4.29.0/void-aarch64-pd-v4.29.0.tar.xz";
+
+        assert_eq!(direct_github_url(proxied), Some(direct));
+        assert_eq!(direct_github_url("https://mirror.example.com/void.tar.xz"), None);
+        assert_eq!(download_candidates(proxied, None), vec![proxied, direct]);
+        assert_eq!(download_candidates(proxied, Some("none")), vec![direct]);
+        assert_eq!(download_candidates(proxied, Some("")), vec![direct]);
+        assert_eq!(
+            download_candidates(direct, Some("https://ghfast.top/")),
+            vec![format!("https://ghfast.top/{}", direct), direct.to_string()]
+        );
+        assert_eq!(download_candidates("https://mirror.example.com/void.tar.xz", Some("none")), vec!["https://mirror.example.com/void.tar.xz"]);
+    }
+
+    #[test]
+    fn test_parse_content_length() {
+        let headers = "HTTP/2 302 \r\nlocation: https://objects.example.com/a.tar.xz\r\ncontent-length: 0\r\n\r\nHTTP/2 200 \r\ncontent-type: application/octet-stream\r\ncontent-length: 31457280\r\n\r\n";
+        assert_eq!(parse_content_length(headers), Some(31457280));
+        assert_eq!(parse_content_length("HTTP/1.1 302 Found\r\nContent-Length: 0\r\n\r\nHTTP/1.1 200 OK\r\n\r\n"), None);
+    }
+
+    #[test]
+    fn test_get_content_length_follows_redirect() {
+        let origin = serve(vec![("/rootfs.tar.xz".to_string(), vec![0; 4096])]);
+        let redirect = serve_redirect(&origin);
+        
+        assert_eq!(get_content_length(&format!("{}/rootfs.tar.xz", redirect), 5).unwrap(), Some(4096));
+        assert_eq!(get_content_length(&format!("{}/missing.tar.xz", redirect), 5).unwrap(), None);
+    }
+
     #[test]
     fn test_download_file_invalid_url() {
         let temp_dir = TempDir::new().unwrap();
@@ -33,4 +67,13 @@ mod tests {
         let result = download_file("https://invalid-url-12345.com/file.txt", output_path.to_str().unwrap());
         assert!(result.is_err());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_find_free_port_skips_used_port() {
+        let listener = std::net::TcpListener::bind(("0.0.0.0", 0)).unwrap();
+        let used = listener.local_addr().unwrap().port();
+        
+        assert!(!is_port_free(used));
+        assert_ne!(find_free_port(used, used.saturating_add(20)), Some(used));
+    }
+}
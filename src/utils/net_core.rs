@@ -1,3 +1,6 @@
+use std::net::TcpListener;
+use std::path::Path;
+use std::time::Duration;
 use std::process::{Command, Stdio};
 use std::io::{BufRead, BufReader};
 
@@ -121,23 +124,124 @@ fn parse_size_with_unit(s: &str) -> Option<u64> {
     }
 }
 
-pub fn get_content_length(url: &str) -> Result<Option<u64>, Box<dyn std::error::Error>> {
+/// Downloads `url` without progress output, following redirects and failing
+/// on HTTP errors instead of saving the error page.
+pub fn fetch_file(url: &str, output_path: &Path, timeout_secs: u64) -> Result<(), Box<dyn std::error::Error>> {
     let output = Command::new("curl")
-        .args(["-sI", url])
+        .args(["-fsSL", "--max-time", &timeout_secs.to_string(), "-o"])
+        .arg(output_path)
+        .arg(url)
         .output()?;
     
     if !output.status.success() {
-        return Ok(None);
+        let reason = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(format!("下载失败: {} {}", url, reason).trim_end().to_string().into());
+    }
+    
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UrlProbe {
+    /// Time until the first byte of the response arrived.
+    pub latency: Duration,
+    pub bytes: u64,
+    pub bytes_per_sec: f64,
+}
+
+/// Downloads `url` to nowhere and reports how fast it came.
+pub fn probe_url(url: &str, timeout_secs: u64) -> Result<UrlProbe, Box<dyn std::error::Error>> {
+    let output = Command::new("curl")
+        .args(["-fsSL", "--max-time", &timeout_secs.to_string(), "-o", "/dev/null"])
+        .args(["-w", "%{time_starttransfer} %{size_download} %{speed_download}"])
+        .arg(url)
+        .output()?;
+    
+    if !output.status.success() {
+        let reason = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(format!("下载失败: {} {}", url, reason).trim_end().to_string().into());
     }
     
     let stdout = String::from_utf8_lossy(&output.stdout);
-    for line in stdout.lines() {
-        if line.starts_with("Content-Length:") {
-            if let Ok(length) = line[16..].trim().parse::<u64>() {
-                return Ok(Some(length));
+    let mut values = stdout.split_whitespace().map(|value| value.parse::<f64>());
+    let (Some(Ok(latency)), Some(Ok(bytes)), Some(Ok(bytes_per_sec))) = (values.next(), values.next(), values.next()) else {
+        return Err(format!("无法解析 curl 输出: {}", stdout).into());
+    };
+    Ok(UrlProbe {
+        latency: Duration::from_secs_f64(latency),
+        bytes: bytes as u64,
+        bytes_per_sec,
+    })
+}
+
+const GITHUB_URL: &str = "https://github.com/";
+
+/// The GitHub URL behind `url`, with any proxy prefix such as
+/// `https://gh-proxy.org/` removed. `None` for URLs not on GitHub.
+pub fn direct_github_url(url: &str) -> Option<&str> {
+    if url.starts_with(GITHUB_URL) {
+        return Some(url);
+    }
+    let start = url.find(&format!("/{}", GITHUB_URL))? + 1;
+    Some(&url[start..])
+}
+
+/// URLs to try for `url` in order: the one rewritten for `github_proxy`
+/// (`None` keeps `url`, empty or `none` means no proxy) followed by the
+/// direct GitHub URL when that differs.
+pub fn download_candidates(url: &str, github_proxy: Option<&str>) -> Vec<String> {
+    let Some(direct) = direct_github_url(url) else {
+        return vec![url.to_string()];
+    };
+    let primary = match github_proxy.map(str::trim) {
+        None => url.to_string(),
+        Some(proxy) if proxy.is_empty() || proxy.eq_ignore_ascii_case("none") => direct.to_string(),
+        Some(proxy) => format!("{}/{}", proxy.trim_end_matches('/'), direct),
+    };
+    
+    let mut candidates = vec![primary];
+    if candidates[0] != direct {
+        candidates.push(direct.to_string());
+    }
+    candidates
+}
+
+/// Size of the file at `url`, following redirects (GitHub release assets
+/// redirect to a storage host). `None` when the final response has no
+/// `Content-Length` or the lookup takes longer than `timeout_secs`.
+pub fn get_content_length(url: &str, timeout_secs: u64) -> Result<Option<u64>, Box<dyn std::error::Error>> {
+    let output = Command::new("curl")
+        .args(["-fsSIL", "--max-time", &timeout_secs.to_string(), url])
+        .output()?;
+    
+    if !output.status.success() {
+        return Ok(None);
+    }
+    
+    Ok(parse_content_length(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// `Content-Length` of the last response in `curl -I -L` output, which
+/// prints the headers of every hop.
+pub fn parse_content_length(headers: &str) -> Option<u64> {
+    let mut length = None;
+    for line in headers.lines() {
+        if line.starts_with("HTTP/") {
+            length = None;
+        } else if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse().ok();
             }
         }
     }
-    
-    Ok(None)
-}
\ No newline at end of file
+    length
+}
+
+pub fn is_port_free(port: u16) -> bool {
+    TcpListener::bind(("0.0.0.0", port)).is_ok()
+}
+
+/// First port in `start..=end` nothing is listening on.
+pub fn find_free_port(start: u16, end: u16) -> Option<u16> {
+    (start..=end).find(|port| is_port_free(*port))
+}
@@ -3,22 +3,6 @@ use std::path::Path;
 use std::fs;
 use std::os::unix::fs::MetadataExt;
 
-pub fn get_current_user() -> Result<String, Box<dyn std::error::Error>> {
-    let output = Command::new("whoami")
-        .output()
-        .map_err(|e| format!("无法执行 whoami: {}", e))?;
-    
-    if !output.status.success() {
-        return Err("whoami 命令执行失败".into());
-    }
-    
-    let user = String::from_utf8_lossy(&output.stdout)
-        .trim()
-        .to_string();
-    
-    Ok(user)
-}
-
 #[allow(dead_code)]
 pub fn get_user_groups() -> Result<Vec<String>, Box<dyn std::error::Error>> {
     let output = Command::new("groups")
@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+use std::fs;
+use std::os::unix::process::CommandExt;
+use std::process::Command;
+use std::thread;
+use std::time::Duration;
+
+/// Fields of `/proc/<pid>/stat` that follow the parenthesised command name,
+/// which itself may contain spaces.
+fn read_stat_fields(pid: u32) -> Option<Vec<String>> {
+    let stat = fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    let after_comm = &stat[stat.rfind(')')? + 1..];
+    Some(after_comm.split_whitespace().map(String::from).collect())
+}
+
+pub fn get_parent_pid(pid: u32) -> Option<u32> {
+    read_stat_fields(pid)?.get(1)?.parse().ok()
+}
+
+/// Start time in clock ticks since boot; together with the PID it identifies
+/// a process even after the PID has been reused.
+pub fn get_process_start_time(pid: u32) -> Option<u64> {
+    read_stat_fields(pid)?.get(19)?.parse().ok()
+}
+
+/// Zombies still have a `/proc` entry until reaped but count as exited.
+pub fn is_process_alive(pid: u32, start_time: Option<u64>) -> bool {
+    let Some(fields) = read_stat_fields(pid) else {
+        return false;
+    };
+    if fields.first().map(String::as_str) == Some("Z") {
+        return false;
+    }
+
+    match (fields.get(19).and_then(|value| value.parse::<u64>().ok()), start_time) {
+        (Some(current), Some(expected)) => current == expected,
+        (Some(_), None) => true,
+        (None, _) => false,
+    }
+}
+
+fn list_pids() -> Vec<u32> {
+    fs::read_dir("/proc")
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter_map(|entry| entry.file_name().to_str()?.parse().ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The process and all of its descendants, parents before children.
+pub fn get_process_tree(root: u32) -> Vec<u32> {
+    let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+    for pid in list_pids() {
+        if let Some(ppid) = get_parent_pid(pid) {
+            children.entry(ppid).or_default().push(pid);
+        }
+    }
+
+    let mut tree = vec![root];
+    let mut index = 0;
+    while index < tree.len() {
+        if let Some(kids) = children.get(&tree[index]) {
+            tree.extend(kids.iter().copied());
+        }
+        index += 1;
+    }
+    tree
+}
+
+const SIGINT: i32 = 2;
+const SIGQUIT: i32 = 3;
+const SIG_DFL: usize = 0;
+const SIG_IGN: usize = 1;
+
+extern "C" {
+    fn signal(signum: i32, handler: usize) -> usize;
+}
+
+/// Keeps Ctrl-C and Ctrl-\ from killing insOs while a foreground guest
+/// runs, so insOs is still there to tear down afterwards. The previous
+/// dispositions come back when the guard is dropped.
+pub struct TerminalSignalGuard {
+    previous: [(i32, usize); 2],
+}
+
+pub fn ignore_terminal_signals() -> TerminalSignalGuard {
+    // SAFETY: `signal` only swaps the disposition; SIG_IGN installs no handler.
+    let previous = [SIGINT, SIGQUIT].map(|signum| (signum, unsafe { signal(signum, SIG_IGN) }));
+    TerminalSignalGuard { previous }
+}
+
+impl Drop for TerminalSignalGuard {
+    fn drop(&mut self) {
+        for (signum, handler) in self.previous {
+            // SAFETY: restores the disposition returned by `signal` above.
+            unsafe { signal(signum, handler) };
+        }
+    }
+}
+
+/// Ignored signals stay ignored across `exec`, so a child started under a
+/// [`TerminalSignalGuard`] has to reset them to get the usual Ctrl-C.
+pub fn restore_terminal_signals(command: &mut Command) {
+    // SAFETY: `signal` is async-signal-safe, as `pre_exec` requires.
+    unsafe {
+        command.pre_exec(|| {
+            signal(SIGINT, SIG_DFL);
+            signal(SIGQUIT, SIG_DFL);
+            Ok(())
+        });
+    }
+}
+
+pub fn send_signal(pid: u32, signal: &str) -> bool {
+    Command::new("kill")
+        .args(["-s", signal, &pid.to_string()])
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+/// Sends TERM to the given processes and all their descendants, escalating
+/// to KILL for anything still alive after `grace`.
+pub fn terminate_process_trees(roots: &[u32], grace: Duration) {
+    let mut pids = Vec::new();
+    for root in roots {
+        pids.extend(get_process_tree(*root));
+    }
+
+    for pid in pids.iter().rev() {
+        send_signal(*pid, "TERM");
+    }
+
+    let step = Duration::from_millis(100);
+    let mut waited = Duration::ZERO;
+    while waited < grace && pids.iter().any(|pid| is_process_alive(*pid, None)) {
+        thread::sleep(step);
+        waited += step;
+    }
+
+    for pid in pids.iter().filter(|pid| is_process_alive(**pid, None)) {
+        send_signal(*pid, "KILL");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_current_process_is_alive() {
+        let pid = std::process::id();
+        let start = get_process_start_time(pid);
+        assert!(start.is_some());
+        assert!(is_process_alive(pid, start));
+        assert!(!is_process_alive(pid, start.map(|s| s + 1)));
+    }
+
+    #[test]
+    fn test_process_tree_contains_children() {
+        let mut child = Command::new("sleep").arg("5").spawn().unwrap();
+        let tree = get_process_tree(std::process::id());
+        assert_eq!(tree[0], std::process::id());
+        assert!(tree.contains(&child.id()));
+
+        assert!(send_signal(child.id(), "TERM"));
+        child.wait().unwrap();
+    }
+
+    fn ignored_signals(pid: u32) -> u64 {
+        let status = fs::read_to_string(format!("/proc/{}/status", pid)).unwrap();
+        let mask = status.lines().find_map(|line| line.strip_prefix("SigIgn:")).unwrap();
+        u64::from_str_radix(mask.trim(), 16).unwrap()
+    }
+
+    #[test]
+    fn test_terminal_signal_guard() {
+        let interrupt_and_quit = (1 << (SIGINT - 1)) | (1 << (SIGQUIT - 1));
+        let guard = ignore_terminal_signals();
+        assert_eq!(ignored_signals(std::process::id()) & interrupt_and_quit, interrupt_and_quit);
+
+        let mut command = Command::new("sleep");
+        command.arg("5");
+        restore_terminal_signals(&mut command);
+        let mut child = command.spawn().unwrap();
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(ignored_signals(child.id()) & interrupt_and_quit, 0);
+        child.kill().unwrap();
+        child.wait().unwrap();
+
+        drop(guard);
+        assert_eq!(ignored_signals(std::process::id()) & interrupt_and_quit, 0);
+    }
+
+    #[test]
+    fn test_missing_process_is_not_alive() {
+        assert!(!is_process_alive(u32::MAX, None));
+        assert_eq!(get_parent_pid(u32::MAX), None);
+    }
+}
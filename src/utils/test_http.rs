@@ -0,0 +1,70 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+use std::time::Duration;
+
+/// Method and path of the request on `stream`, with its headers consumed.
+fn read_request(stream: &TcpStream) -> std::io::Result<(String, String)> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    Ok((parts.next().unwrap_or_default().to_string(), parts.next().unwrap_or("/").to_string()))
+}
+
+fn respond(stream: TcpStream, routes: &[(String, Vec<u8>)], delay: Duration) -> std::io::Result<()> {
+    let (method, path) = read_request(&stream)?;
+    let (status, body) = match routes.iter().find(|(route, _)| *route == path) {
+        Some((_, body)) => ("200 OK", body.as_slice()),
+        None => ("404 Not Found", &b"not found"[..]),
+    };
+
+    thread::sleep(delay);
+    let mut stream = stream;
+    write!(stream, "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n", status, body.len())?;
+    if method != "HEAD" {
+        stream.write_all(body)?;
+    }
+    stream.flush()
+}
+
+/// Serves `routes` (path and body) on a free local port for the rest of the
+/// test process and returns the base URL, e.g. `http://127.0.0.1:40123`.
+pub fn serve(routes: Vec<(String, Vec<u8>)>) -> String {
+    serve_with_delay(routes, Duration::ZERO)
+}
+
+/// Like [`serve`], but waits `delay` before answering each request, to
+/// stand in for a slow or distant server.
+pub fn serve_with_delay(routes: Vec<(String, Vec<u8>)>, delay: Duration) -> String {
+    let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let _ = respond(stream, &routes, delay);
+        }
+    });
+    base_url
+}
+
+/// Answers every request with a redirect to the same path under `target`.
+pub fn serve_redirect(target: &str) -> String {
+    let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let target = target.to_string();
+    thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            if let Ok((_, path)) = read_request(&stream) {
+                let _ = write!(stream, "HTTP/1.1 302 Found\r\nLocation: {}{}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n", target, path);
+            }
+        }
+    });
+    base_url
+}